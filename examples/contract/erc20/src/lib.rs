@@ -177,6 +177,12 @@ mod erc20 {
             println!("{:?}", transfer_event);
             assert_eq!(transfer_event.decode_data::<u128>(), 10);
 
+            let transfers = test::events().decode::<Transfer>();
+            assert_eq!(transfers.len(), 1);
+            assert_eq!(transfers[0].from, alice);
+            assert_eq!(transfers[0].to, bob);
+            assert_eq!(transfers[0].value, 10);
+
             assert_eq!(contract.balance_of(bob.clone()), 10);
             assert_eq!(contract.balance_of(alice.clone()), 90);
         }
@@ -247,5 +253,31 @@ mod erc20 {
             assert_eq!(contract.balance_of(charlie), 10);
             assert_eq!(contract.balance_of(bob), 0);
         }
+
+        #[cfg(feature = "fuzz")]
+        #[test]
+        fn total_supply_is_conserved() {
+            let report = liquid::fuzz::Harness::new(|| {
+                test::set_caller(test::default_accounts().alice);
+                Erc20::new(1000)
+            })
+            .invariant("balances sum up to total supply", |contract| {
+                let accounts = test::default_accounts();
+                let sum: Balance = [
+                    accounts.alice,
+                    accounts.bob,
+                    accounts.charlie,
+                    accounts.david,
+                    accounts.eve,
+                    accounts.frank,
+                ]
+                .iter()
+                .map(|owner| contract.balance_of(owner.clone()))
+                .sum();
+                contract.total_supply == sum
+            })
+            .run();
+            assert!(report.calls > 0);
+        }
     }
 }
@@ -30,14 +30,18 @@ impl<'a> common::GenerateCode for Dispatch<'a> {
         let marker = self.generate_external_fn_marker();
         let traits = self.generate_external_fn_traits();
         let dispatch = self.generate_dispatch();
+        let deploy = self.generate_deploy();
         let entry_point = self.generate_entry_point();
+        let contract_entry = self.generate_contract_entry();
 
         quote! {
-            #[cfg(not(test))]
+            #contract_entry
+
             const _: () = {
                 #marker
                 #traits
                 #dispatch
+                #deploy
                 #entry_point
             };
         }
@@ -217,13 +221,7 @@ impl<'a> Dispatch<'a> {
         }
     }
 
-    #[cfg(feature = "std")]
-    fn generate_entry_point(&self) -> TokenStream2 {
-        quote!()
-    }
-
-    #[cfg(not(feature = "std"))]
-    fn generate_entry_point(&self) -> TokenStream2 {
+    fn generate_deploy(&self) -> TokenStream2 {
         let constr = &self.contract.constructor;
         let constr_sig = &constr.sig;
         let ident = &constr_sig.ident;
@@ -239,6 +237,12 @@ impl<'a> Dispatch<'a> {
                 }
             })
             .collect();
+        let record_schema_version =
+            self.contract.storage.schema_version.map(|schema_version| {
+                quote! {
+                    liquid_lang::storage::set_schema_version(#schema_version);
+                }
+            });
 
         let constr_input_tys = common::generate_input_tys(&constr_sig);
         let constr_input_idents = common::generate_input_idents(&constr_sig);
@@ -256,6 +260,76 @@ impl<'a> Dispatch<'a> {
             }
         });
 
+        quote! {
+            impl Storage {
+                pub fn __liquid_deploy() {
+                    let mut storage = <Storage as liquid_lang::storage::New>::new();
+                    let call_data = liquid_lang::env::get_call_data(liquid_lang::env::CallMode::Deploy);
+                    if let Ok(call_data) = call_data {
+                        let data = call_data.data;
+                        let data_ptr = &mut data.as_slice();
+                        #(#constr_inputs)*
+                        storage.#ident(#(#constr_input_idents,)*);
+                        <Storage as liquid_lang::storage::Flush>::flush(&mut storage);
+                        #record_schema_version
+                    } else {
+                        liquid_lang::env::revert(&String::from("could not read input"));
+                    }
+                    #(#asset_registers)*
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn generate_contract_entry(&self) -> TokenStream2 {
+        quote! {
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            pub struct __LIQUID_CONTRACT_ENTRY;
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn generate_contract_entry(&self) -> TokenStream2 {
+        quote!()
+    }
+
+    #[cfg(feature = "std")]
+    fn generate_entry_point(&self) -> TokenStream2 {
+        let method_names = self.contract.functions.iter().filter_map(|func| {
+            let fn_id = match &func.kind {
+                FunctionKind::External(fn_id) => fn_id,
+                _ => return None,
+            };
+            let fn_name = func.sig.ident.to_string();
+            Some(quote! {
+                if selector == <FnMarker<[(); #fn_id]> as liquid_lang::FnSelector>::SELECTOR {
+                    return Some(#fn_name);
+                }
+            })
+        });
+
+        quote! {
+            impl liquid_lang::env::test::ContractEntry for __LIQUID_CONTRACT_ENTRY {
+                fn deploy() {
+                    Storage::__liquid_deploy();
+                }
+
+                fn dispatch() -> liquid_lang::DispatchResult {
+                    Storage::__liquid_dispatch()
+                }
+
+                fn method_name(selector: liquid_primitives::Selector) -> Option<&'static str> {
+                    #(#method_names)*
+                    None
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn generate_entry_point(&self) -> TokenStream2 {
         quote! {
             #[no_mangle]
             fn hash_type() -> u32 {
@@ -268,18 +342,7 @@ impl<'a> Dispatch<'a> {
 
             #[no_mangle]
             fn deploy() {
-                let mut storage = <Storage as liquid_lang::storage::New>::new();
-                let call_data = liquid_lang::env::get_call_data(liquid_lang::env::CallMode::Deploy);
-                if let Ok(call_data) = call_data {
-                    let data = call_data.data;
-                    let data_ptr = &mut data.as_slice();
-                    #(#constr_inputs)*
-                    storage.#ident(#(#constr_input_idents,)*);
-                    <Storage as liquid_lang::storage::Flush>::flush(&mut storage);
-                } else {
-                    liquid_lang::env::revert(&String::from("could not read input"));
-                }
-                #(#asset_registers)*
+                Storage::__liquid_deploy();
             }
 
             #[no_mangle]
@@ -29,6 +29,7 @@ impl<'a> GenerateCode for Events<'a> {
 
         let event_enum = self.generate_event_enum();
         let topics_impls = self.generate_topics_impls();
+        let decode_impls = self.generate_decode_impls();
         let emit_trait = self.generate_emit_trait();
 
         quote! {
@@ -37,6 +38,7 @@ impl<'a> GenerateCode for Events<'a> {
                 use super::*;
 
                 #(#topics_impls)*
+                #decode_impls
                 #event_enum
                 #emit_trait
             }
@@ -78,6 +80,21 @@ impl<'a> Events<'a> {
             .map(|item_event| &item_event.ident)
             .collect::<Vec<_>>();
 
+        // Only the test environment needs to decode emitted events.
+        let encode_fields = if cfg!(feature = "std") {
+            quote! {
+                fn encode_fields(&self) -> liquid_prelude::vec::Vec<u8> {
+                    match self {
+                        #(
+                            Event::#event_idents(event) => event.encode_fields(),
+                        )*
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
         quote! {
             pub enum Event {
                 #(#event_idents(#event_idents),)*
@@ -99,6 +116,8 @@ impl<'a> Events<'a> {
                         )*
                     }
                 }
+
+                #encode_fields
             }
 
             impl scale::Encode for Event {
@@ -113,6 +132,37 @@ impl<'a> Events<'a> {
         }
     }
 
+    fn generate_decode_impls(&self) -> TokenStream2 {
+        if !cfg!(feature = "std") {
+            return quote! {};
+        }
+
+        let impls = self.contract.events.iter().map(|item_event| {
+            let event_ident = &item_event.ident;
+            let event_name = event_ident.to_string();
+            let event_name_bytes = event_name.as_bytes();
+            let field_idents = item_event.fields.iter().map(|field| &field.ident);
+
+            quote! {
+                impl liquid_lang::env::test::DecodeEvent for #event_ident {
+                    fn signature() -> liquid_primitives::types::Hash {
+                        liquid_primitives::hash::hash(&[#(#event_name_bytes),*]).into()
+                    }
+
+                    fn decode_fields(fields: &mut &[u8]) -> ::core::result::Result<Self, scale::Error> {
+                        Ok(Self {
+                            #(#field_idents: scale::Decode::decode(fields)?,)*
+                        })
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #(#impls)*
+        }
+    }
+
     fn generate_topics_impls(&'a self) -> impl Iterator<Item = TokenStream2> + 'a {
         self.contract.events.iter().map(move |item_event| {
             let span = item_event.span;
@@ -157,11 +207,25 @@ impl<'a> Events<'a> {
                 }
             };
 
+            let encode_fields = if cfg!(feature = "std") {
+                let field_idents = event_fields.iter().map(|field| &field.ident);
+                quote! {
+                    fn encode_fields(&self) -> liquid_prelude::vec::Vec<u8> {
+                        let mut fields = liquid_prelude::vec::Vec::new();
+                        #(scale::Encode::encode_to(&self.#field_idents, &mut fields);)*
+                        fields
+                    }
+                }
+            } else {
+                quote! {}
+            };
             let impls = quote_spanned! { span =>
                 impl liquid_primitives::Topics for #event_ident {
                     fn topics(&self) -> liquid_prelude::vec::Vec<liquid_primitives::types::Hash> {
                         [#sig_hash, #topic_hash].to_vec()
                     }
+
+                    #encode_fields
                 }
             };
 
@@ -40,6 +40,14 @@ impl GenerateCode for ir::Contract {
         let testable = Testable::from(self).generate_code();
         let abi = AbiGen::from(self).generate_code();
         let rust_items = &self.rust_items;
+        let entry_export = if cfg!(feature = "std") {
+            quote! {
+                #[doc(hidden)]
+                pub use __liquid_private::__LIQUID_CONTRACT_ENTRY;
+            }
+        } else {
+            quote!()
+        };
 
         quote! {
             mod #ident {
@@ -68,6 +76,8 @@ impl GenerateCode for ir::Contract {
                 #[cfg(feature = "liquid-abi-gen")]
                 pub use __liquid_private::__LIQUID_ABI_GEN;
 
+                #entry_export
+
                 #event_struct
 
                 #(#rust_items)*
@@ -80,9 +80,10 @@ impl<'a> Storage<'a> {
             .map(|field| field.ty.clone())
             .collect::<Vec<_>>();
 
-        let keys = field_idents
+        let keys = storage
+            .field_keys
             .iter()
-            .map(|ident| syn::LitStr::new(ident.to_string().as_str(), Span::call_site()))
+            .map(|key| syn::LitStr::new(key.as_str(), Span::call_site()))
             .collect::<Punctuated<syn::LitStr, Token![,]>>();
         let keys_count = keys.len();
 
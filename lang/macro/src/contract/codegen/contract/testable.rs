@@ -11,12 +11,15 @@
 // limitations under the License.
 
 use crate::{
-    common::GenerateCode,
-    contract::ir::{Contract, FnArg},
+    common::{self, GenerateCode},
+    contract::{
+        ir::{Contract, FnArg, FunctionKind},
+        MIGRATE_NAME,
+    },
     utils as lang_utils,
 };
 use derive_more::From;
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use quote::quote;
 
 #[derive(From)]
@@ -28,6 +31,17 @@ impl<'a> GenerateCode for Testable<'a> {
     fn generate_code(&self) -> TokenStream2 {
         let testable_storage = self.generate_testable_storage();
         let constructor = self.generate_constructor();
+        let migrate_schema = self.generate_migrate_schema();
+        let contract_entry = if cfg!(feature = "std") {
+            self.generate_contract_entry()
+        } else {
+            quote! {}
+        };
+        let fuzzable = if cfg!(feature = "fuzz") {
+            self.generate_fuzzable()
+        } else {
+            quote! {}
+        };
 
         quote! {
             #[cfg(test)]
@@ -38,7 +52,11 @@ impl<'a> GenerateCode for Testable<'a> {
 
                 impl TestableStorage {
                     #constructor
+                    #migrate_schema
                 }
+
+                #contract_entry
+                #fuzzable
             }
 
             #[cfg(test)]
@@ -94,15 +112,221 @@ impl<'a> Testable<'a> {
             _ => unreachable!(),
         });
 
+        let record_schema_version =
+            self.contract.storage.schema_version.map(|schema_version| {
+                quote! {
+                    liquid_lang::storage::set_schema_version(#schema_version);
+                }
+            });
+
         quote! {
             #(#attrs)*
             pub fn #ident(#(#args,)*) -> Self {
                 let mut contract = <Storage as liquid_lang::storage::New>::new();
                 contract.#ident(#(#arg_idents,)*);
+                #record_schema_version
                 Self {
                     contract
                 }
             }
         }
     }
+
+    /// Generates the implementation of `ContractEntry` for the testable storage,
+    /// so that the contract can be registered in the test environment via
+    /// `test::register_contract::<Contract>(addr)`.
+    fn generate_contract_entry(&self) -> TokenStream2 {
+        quote! {
+            impl liquid_lang::env::test::ContractEntry for TestableStorage {
+                fn deploy() {
+                    <__LIQUID_CONTRACT_ENTRY as liquid_lang::env::test::ContractEntry>::deploy()
+                }
+
+                fn dispatch() -> liquid_lang::DispatchResult {
+                    <__LIQUID_CONTRACT_ENTRY as liquid_lang::env::test::ContractEntry>::dispatch()
+                }
+
+                fn method_name(selector: liquid_primitives::Selector) -> Option<&'static str> {
+                    <__LIQUID_CONTRACT_ENTRY as liquid_lang::env::test::ContractEntry>::method_name(selector)
+                }
+            }
+        }
+    }
+
+    /// Generates `load` and `migrate_schema` for contracts with a schema
+    /// version. `load` binds the storage written by a previous version of the
+    /// contract without running the constructor, and `migrate_schema` runs the
+    /// migration of the storage as if `__liquid_migrate` was called on chain.
+    fn generate_migrate_schema(&self) -> TokenStream2 {
+        if self.contract.storage.schema_version.is_none() {
+            return quote! {};
+        }
+
+        let migrate_name = Ident::new(MIGRATE_NAME, Span::call_site());
+        quote! {
+            pub fn load() -> Self {
+                Self {
+                    contract: <Storage as liquid_lang::storage::New>::new(),
+                }
+            }
+
+            pub fn migrate_schema(&mut self) -> bool {
+                self.contract.#migrate_name()
+            }
+        }
+    }
+
+    /// Generates a `FuzzCall` enum whose variants correspond to the external
+    /// methods taking `&mut self`, and implements `Fuzzable` for the testable
+    /// storage with it.
+    fn generate_fuzzable(&self) -> TokenStream2 {
+        let mut_fns = self
+            .contract
+            .functions
+            .iter()
+            .filter(|func| {
+                matches!(&func.kind, FunctionKind::External(..))
+                    && func.sig.is_mut()
+                    && !func.is_internal_fn()
+            })
+            .collect::<Vec<_>>();
+        if mut_fns.is_empty() {
+            return quote! {};
+        }
+
+        let fn_idents = mut_fns
+            .iter()
+            .map(|func| &func.sig.ident)
+            .collect::<Vec<_>>();
+        let input_tys = mut_fns
+            .iter()
+            .map(|func| common::generate_input_tys(&func.sig))
+            .collect::<Vec<_>>();
+        let arg_idents = input_tys
+            .iter()
+            .map(|tys| {
+                (0..tys.len())
+                    .map(|i| Ident::new(&format!("__liquid_arg_{i}"), Span::call_site()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let variants = fn_idents.iter().zip(input_tys.iter()).map(|(ident, tys)| {
+            quote! {
+                #ident(#(#tys,)*)
+            }
+        });
+        let arbitrary_arms = fn_idents.iter().zip(input_tys.iter()).enumerate().map(
+            |(i, (ident, tys))| {
+                let i = i as u64;
+                quote! {
+                    #i => FuzzCall::#ident(
+                        #(<#tys as liquid_lang::fuzz::Arbitrary>::arbitrary(rng),)*
+                    ),
+                }
+            },
+        );
+        let shrink_arms = fn_idents
+            .iter()
+            .zip(arg_idents.iter())
+            .map(|(ident, args)| {
+                let shrink_args = args.iter().enumerate().map(|(i, arg)| {
+                    let others = args.iter().enumerate().map(|(j, other)| {
+                        if i == j {
+                            quote! { shrunk_arg }
+                        } else {
+                            quote! { #other.clone() }
+                        }
+                    });
+                    quote! {
+                        for shrunk_arg in liquid_lang::fuzz::Arbitrary::shrink(#arg) {
+                            shrunk.push(FuzzCall::#ident(#(#others,)*));
+                        }
+                    }
+                });
+                quote! {
+                    FuzzCall::#ident(#(#args,)*) => {
+                        #(#shrink_args)*
+                    }
+                }
+            });
+        let describe_arms =
+            fn_idents
+                .iter()
+                .zip(arg_idents.iter())
+                .map(|(ident, args)| {
+                    let fn_name = ident.to_string();
+                    quote! {
+                        FuzzCall::#ident(#(#args,)*) => {
+                            let mut call = f.debug_tuple(#fn_name);
+                            #(call.field(&liquid_lang::fuzz::Describe(#args));)*
+                            call.finish()
+                        }
+                    }
+                });
+        let apply_arms = fn_idents
+            .iter()
+            .zip(arg_idents.iter())
+            .map(|(ident, args)| {
+                quote! {
+                    FuzzCall::#ident(#(#args,)*) => {
+                        #[allow(deprecated)]
+                        let _ = self.contract.#ident(#(#args,)*);
+                    }
+                }
+            });
+        let variants_count = mut_fns.len() as u64;
+
+        quote! {
+            #[allow(non_camel_case_types)]
+            #[derive(Clone)]
+            pub enum FuzzCall {
+                #(#variants,)*
+            }
+
+            impl liquid_lang::fuzz::Arbitrary for FuzzCall {
+                fn arbitrary(rng: &mut liquid_lang::fuzz::Rng) -> Self {
+                    match rng.below(#variants_count) {
+                        #(#arbitrary_arms)*
+                        _ => unreachable!(),
+                    }
+                }
+
+                #[allow(unused_mut)]
+                fn shrink(&self) -> Vec<Self> {
+                    let mut shrunk = Vec::new();
+                    match self {
+                        #(#shrink_arms)*
+                    }
+                    shrunk
+                }
+
+                fn describe(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                    match self {
+                        #(#describe_arms)*
+                    }
+                }
+            }
+
+            impl liquid_lang::fuzz::Fuzzable for TestableStorage {
+                type Call = FuzzCall;
+
+                fn load() -> Self {
+                    Self {
+                        contract: <Storage as liquid_lang::storage::New>::new(),
+                    }
+                }
+
+                fn flush(&mut self) {
+                    liquid_lang::storage::Flush::flush(&mut self.contract);
+                }
+
+                fn apply(&mut self, call: &FuzzCall) {
+                    match call.clone() {
+                        #(#apply_arms)*
+                    }
+                }
+            }
+        }
+    }
 }
@@ -117,6 +117,7 @@ fn generate_mock_common(foreign_fn: &ForeignFn) -> TokenStream2 {
             });
 
     let useless_params = input_idents.iter().map(|_| quote! {_});
+    let is_static = !sig.is_mut();
     let inputs = inputs.iter().skip(1);
 
     quote! {
@@ -142,6 +143,7 @@ fn generate_mock_common(foreign_fn: &ForeignFn) -> TokenStream2 {
             Default,
             Func(Box<dyn FnMut(#(#input_tys,)*) -> #output_ty + 'static>),
             Exception,
+            Revert(String),
         }
 
         pub struct Expectation {
@@ -159,18 +161,25 @@ fn generate_mock_common(foreign_fn: &ForeignFn) -> TokenStream2 {
         }
 
         impl Expectation {
-            pub fn call(&mut self, #(#inputs,)*) -> Option<#output_ty> {
+            pub fn call(&mut self, #(#inputs,)*) -> liquid_lang::env::error::Result<#output_ty> {
                 match self.return_fn {
                     Returner::Default => {
                         let default_value = DefaultReturner::<#output_ty>::return_default();
                         if let Some(default_value) = default_value {
-                            Some(default_value)
+                            Ok(default_value)
                         } else {
                             panic!("can only return default values for types that impl `std::Default`");
                         }
                     }
-                    Returner::Func(ref mut f) => Some(f(#(#input_idents,)*)),
-                    Returner::Exception => None,
+                    Returner::Func(ref mut f) => {
+                        liquid_lang::env::test::mock_call(#is_static, || f(#(#input_idents,)*))
+                    }
+                    Returner::Exception => {
+                        Err(liquid_lang::env::error::EnvError::FailToCallForeignContract)
+                    }
+                    Returner::Revert(ref msg) => {
+                        Err(liquid_lang::env::error::EnvError::CallReverted(msg.clone()))
+                    }
                 }
             }
 
@@ -212,14 +221,23 @@ fn generate_mock_common(foreign_fn: &ForeignFn) -> TokenStream2 {
             pub fn throws(&mut self) {
                 self.return_fn = Returner::Exception;
             }
+
+            pub fn reverts<T>(&mut self, msg: T)
+            where
+                T: Into<String>,
+            {
+                self.return_fn = Returner::Revert(msg.into());
+            }
         }
     }
 }
 
 fn generate_trivial_fn(foreign_fn: &ForeignFn, interface_ident: &Ident) -> TokenStream2 {
-    let attrs = lang_utils::filter_non_liquid_attributes(foreign_fn.attrs.iter());
+    let attrs = lang_utils::filter_non_liquid_attributes(foreign_fn.attrs.iter())
+        .collect::<Vec<_>>();
     let sig = &foreign_fn.sig;
     let fn_ident = &sig.ident;
+    let try_fn_ident = Ident::new(&format!("try_{fn_ident}"), fn_ident.span());
     let span = foreign_fn.span;
 
     let mock_context_getter = match &foreign_fn.mock_context_getter {
@@ -239,7 +257,7 @@ fn generate_trivial_fn(foreign_fn: &ForeignFn, interface_ident: &Ident) -> Token
     } else {
         quote_spanned!(span => &self)
     };
-    let actual_inputs = inputs.iter().skip(1);
+    let actual_inputs = inputs.iter().skip(1).collect::<Vec<_>>();
 
     let output = &sig.output;
     let output_ty = match output {
@@ -289,6 +307,15 @@ fn generate_trivial_fn(foreign_fn: &ForeignFn, interface_ident: &Ident) -> Token
                 #(#attrs)*
                 #[allow(non_snake_case)]
                 pub fn #fn_ident(#receiver, #(#actual_inputs,)*) -> Option<#output_ty> {
+                    self.#try_fn_ident(#(#input_idents,)*).ok()
+                }
+
+                #(#attrs)*
+                #[allow(non_snake_case)]
+                pub fn #try_fn_ident(
+                    #receiver,
+                    #(#actual_inputs,)*
+                ) -> liquid_lang::env::error::Result<#output_ty> {
                     EXPECTATIONS.with(|expectations| {
                         for expectation in expectations.borrow_mut().iter_mut() {
                             if expectation.matches(#(#ref_input_idents,)*) {
@@ -322,6 +349,30 @@ impl<'a> Mockable<'a> {
             .values()
             .map(|foreign_fn| generate_trivial_fn(foreign_fn, interface_ident))
             .collect::<Vec<_>>();
+        let deploy_mocks = interface.constructor.as_ref().map(|constructor| {
+            let actual_inputs = constructor.sig.inputs.iter().skip(1).collect::<Vec<_>>();
+
+            // Deployments always succeed in unit tests, the returned instance
+            // behaves the same as the ones created by `at`.
+            quote_spanned! { constructor.span =>
+                #[allow(unused_variables)]
+                pub fn deploy(
+                    code: &[u8],
+                    #(#actual_inputs,)*
+                ) -> liquid_lang::env::error::Result<Self> {
+                    Ok(Self {})
+                }
+
+                #[allow(unused_variables)]
+                pub fn deploy_with_salt(
+                    code: &[u8],
+                    salt: &liquid_primitives::types::Hash,
+                    #(#actual_inputs,)*
+                ) -> liquid_lang::env::error::Result<Self> {
+                    Ok(Self {})
+                }
+            }
+        });
 
         quote_spanned! { span =>
             #[derive(Debug, Clone)]
@@ -331,6 +382,8 @@ impl<'a> Mockable<'a> {
                 pub fn at(_: liquid_primitives::types::Address) -> Self {
                     Self {}
                 }
+
+                #deploy_mocks
             }
 
             impl From<liquid_primitives::types::Address> for Interface {
@@ -148,11 +148,19 @@ impl Interface {
         })
     }
 
+    /// Generates the stub of a foreign method along with its `try_` variant.
+    ///
+    /// The `try_` variant returns the error of the call on failure, e.g., the
+    /// revert message of the callee, while the stub itself simply returns
+    /// `None`. Methods with `&self` receiver are called via static calls
+    /// wherever the `staticCall` host function is available.
     fn generate_trivial_fn(&self, foreign_fn: &ForeignFn) -> TokenStream2 {
-        let attrs = utils::filter_non_liquid_attributes(foreign_fn.attrs.iter());
+        let attrs = utils::filter_non_liquid_attributes(foreign_fn.attrs.iter())
+            .collect::<Vec<_>>();
         let sig = &foreign_fn.sig;
         let span = foreign_fn.span;
         let fn_ident = &sig.ident;
+        let try_fn_ident = Ident::new(&format!("try_{fn_ident}"), fn_ident.span());
 
         let inputs = &sig.inputs;
         let input_tys = common::generate_input_tys(sig);
@@ -194,13 +202,28 @@ impl Interface {
         } else {
             quote_spanned!(span => &self)
         };
-        let actual_inputs = inputs.iter().skip(1);
+        let actual_inputs = inputs.iter().skip(1).collect::<Vec<_>>();
+        let call = if is_mut || !cfg!(any(feature = "std", feature = "unstable-host-api"))
+        {
+            quote! { liquid_lang::env::call }
+        } else {
+            quote! { liquid_lang::env::call_static }
+        };
 
         quote_spanned! { span =>
             #(#attrs)*
             #[allow(non_snake_case)]
-            #[allow(unused_mut)]
             pub fn #fn_ident(#receiver, #(#actual_inputs,)*) -> Option<#output_ty> {
+                self.#try_fn_ident(#(#input_idents,)*).ok()
+            }
+
+            #(#attrs)*
+            #[allow(non_snake_case)]
+            #[allow(unused_mut)]
+            pub fn #try_fn_ident(
+                #receiver,
+                #(#actual_inputs,)*
+            ) -> liquid_lang::env::error::Result<#output_ty> {
                 #[allow(dead_code)]
                 struct __LiquidInputTyChecker #input_ty_checker;
 
@@ -217,7 +240,59 @@ impl Interface {
                 #(
                     __liquid_encoded.extend(#input_encodes);
                 )*
-                liquid_lang::env::call::<#output_ty>(&self.0, &__liquid_encoded).ok()
+                #call::<#output_ty>(&self.0, &__liquid_encoded)
+            }
+        }
+    }
+
+    /// Generates `deploy` and `deploy_with_salt` methods for the interface if
+    /// the constructor is declared, which deploy a new instance of the foreign
+    /// contract with its code via `env::create` and `env::create2`.
+    ///
+    /// FISCO BCOS doesn't provide any host function to deploy contracts from
+    /// contracts yet, so these methods are only generated off chain.
+    fn generate_deploy_fns(&self) -> TokenStream2 {
+        let constructor = match &self.constructor {
+            Some(constructor) if cfg!(feature = "std") => constructor,
+            _ => return quote! {},
+        };
+        let attrs = utils::filter_non_liquid_attributes(constructor.attrs.iter())
+            .collect::<Vec<_>>();
+        let sig = &constructor.sig;
+        let span = constructor.span;
+
+        let input_tys = common::generate_input_tys(sig);
+        let input_ty_checker = common::generate_ty_checker(input_tys.as_slice());
+        let input_idents = common::generate_input_idents(sig);
+        let actual_inputs = sig.inputs.iter().skip(1).collect::<Vec<_>>();
+        let encode_params = quote! {
+            #[allow(dead_code)]
+            struct __LiquidInputTyChecker #input_ty_checker;
+
+            let mut __liquid_encoded = Vec::new();
+            #(
+                __liquid_encoded.extend(<#input_tys as scale::Encode>::encode(&#input_idents));
+            )*
+        };
+
+        quote_spanned! { span =>
+            #(#attrs)*
+            pub fn deploy(
+                code: &[u8],
+                #(#actual_inputs,)*
+            ) -> liquid_lang::env::error::Result<Self> {
+                #encode_params
+                liquid_lang::env::create(code, &__liquid_encoded).map(Self::at)
+            }
+
+            #(#attrs)*
+            pub fn deploy_with_salt(
+                code: &[u8],
+                salt: &liquid_primitives::types::Hash,
+                #(#actual_inputs,)*
+            ) -> liquid_lang::env::error::Result<Self> {
+                #encode_params
+                liquid_lang::env::create2(code, &__liquid_encoded, salt).map(Self::at)
             }
         }
     }
@@ -230,6 +305,7 @@ impl Interface {
             .values()
             .map(|foreign_fn| self.generate_trivial_fn(foreign_fn))
             .collect::<Vec<_>>();
+        let deploy_fns = self.generate_deploy_fns();
 
         let impls = quote_spanned! { span =>
             #[derive(Clone)]
@@ -280,6 +356,7 @@ impl Interface {
             impl liquid_lang::You_Should_Use_An_Valid_State_Type for Interface {}
 
             impl Interface {
+                #deploy_fns
                 #(#trivial_fns)*
             }
         };
@@ -13,7 +13,7 @@
 use crate::{
     contract::{
         ir::{self, utils as ir_utils},
-        SUPPORTS_ASSET_NAME, SUPPORTS_ASSET_SIGNATURE,
+        MIGRATE_NAME, MIGRATE_SIGNATURE, SUPPORTS_ASSET_NAME, SUPPORTS_ASSET_SIGNATURE,
     },
     utils as lang_utils,
 };
@@ -215,6 +215,64 @@ impl TryFrom<(ir::ContractParams, syn::ItemMod)> for ir::Contract {
             span,
         });
 
+        if let Some(schema_version) = storage.schema_version {
+            let migrations = storage.migrated_fields.iter().map(|(index, old_ty)| {
+                let ident = storage.fields.named[*index].ident.as_ref().unwrap();
+                quote! {
+                    liquid_lang::storage::MigrateFrom::migrate_from(
+                        &mut self.#ident,
+                        <#old_ty as liquid_lang::storage::Bind>::bind_with(
+                            Self::STORAGE_KEYS[#index].as_bytes(),
+                        ),
+                    );
+                }
+            });
+            // The existence of the guard is ensured when parsing the storage
+            // struct.
+            let migration_guard = storage.migration_guard.as_ref().unwrap();
+            match functions
+                .iter()
+                .find(|func| &func.sig.ident == migration_guard)
+            {
+                None => bail!(
+                    migration_guard,
+                    "the migration guard `{}` is not defined in methods of the contract",
+                    migration_guard
+                ),
+                Some(func) if func.sig.inputs.len() != 1 => bail_span!(
+                    func.span(),
+                    "the migration guard should take no parameters other than the \
+                     receiver and return `bool`"
+                ),
+                _ => (),
+            }
+            let migrate_name = Ident::new(MIGRATE_NAME, Span::call_site());
+            let migrate_fn = syn::parse2::<syn::ItemFn>(quote! {
+                pub fn #migrate_name(&mut self) -> bool {
+                    liquid_lang::intrinsics::require(
+                        self.#migration_guard(),
+                        "the caller is not allowed to migrate the storage",
+                    );
+                    if liquid_lang::storage::schema_version() >= #schema_version {
+                        return false;
+                    }
+                    #(#migrations)*
+                    liquid_lang::storage::set_schema_version(#schema_version);
+                    true
+                }
+            })
+            .unwrap();
+            functions.push(ir::Function {
+                attrs: migrate_fn.attrs,
+                kind: ir::FunctionKind::External(lang_utils::calculate_fn_id(
+                    &MIGRATE_SIGNATURE,
+                )),
+                sig: ir::Signature::try_from(&migrate_fn.sig).unwrap(),
+                body: *migrate_fn.block,
+                span,
+            });
+        }
+
         let (mut constructor, mut external_func_count) = (None, 0);
         for (pos, func) in functions.iter().enumerate() {
             match func.kind {
@@ -648,10 +706,139 @@ impl TryFrom<syn::ItemStruct> for ir::ItemStorage {
             )
         }
 
+        let mut schema_version = None;
+        let mut migration_guard = None;
+        for marker in ir_utils::filter_map_liquid_attributes(&item_struct.attrs)? {
+            if marker.ident == "migration_guard" {
+                if migration_guard.is_some() {
+                    bail_span!(
+                        marker.ident.span(),
+                        "duplicate `migration_guard` attribute found here"
+                    )
+                }
+                match &marker.value {
+                    ir::AttrValue::LitStr(lit_str) => {
+                        migration_guard = Some(lit_str.parse::<Ident>()?)
+                    }
+                    _ => bail_span!(
+                        marker.ident.span(),
+                        "the value of `migration_guard` should be a literal string \
+                         naming a method, e.g. `#[liquid(migration_guard = \
+                         \"only_owner\")]`"
+                    ),
+                }
+                continue;
+            }
+            if marker.ident != "schema_version" {
+                continue;
+            }
+            if schema_version.is_some() {
+                bail_span!(
+                    marker.ident.span(),
+                    "duplicate `schema_version` attribute found here"
+                )
+            }
+            match &marker.value {
+                ir::AttrValue::LitInt(lit_int) => match lit_int.base10_parse::<u32>() {
+                    Ok(version) if version > 0 => schema_version = Some(version),
+                    _ => bail!(
+                        lit_int,
+                        "the schema version should be a positive integer which fits in \
+                         `u32`"
+                    ),
+                },
+                _ => bail_span!(
+                    marker.ident.span(),
+                    "the value of `schema_version` should be an integer literal, e.g. \
+                     `#[liquid(schema_version = 2)]`"
+                ),
+            }
+        }
+
+        match (&schema_version, &migration_guard) {
+            (Some(_), None) => bail!(
+                item_struct.ident,
+                "a storage struct with `schema_version` must specify the method which \
+                 authorizes the migration via `#[liquid(migration_guard = \"...\")]`"
+            ),
+            (None, Some(migration_guard)) => bail!(
+                migration_guard,
+                "`migration_guard` requires the storage struct to be marked with \
+                 `#[liquid(schema_version = ...)]`"
+            ),
+            _ => (),
+        }
+
         let mut public_fields = Vec::new();
+        let mut field_keys = Vec::new();
+        let mut migrated_fields = Vec::new();
         let span = item_struct.span();
         let fields = match item_struct.fields {
-            syn::Fields::Named(named_fields) => {
+            syn::Fields::Named(mut named_fields) => {
+                let mut unique_keys = HashSet::new();
+                for (i, field) in named_fields.named.iter_mut().enumerate() {
+                    let mut field_key = field.ident.as_ref().unwrap().to_string();
+                    for marker in ir_utils::filter_map_liquid_attributes(&field.attrs)? {
+                        match (marker.ident.to_string().as_str(), &marker.value) {
+                            ("rename_from", ir::AttrValue::LitStr(lit_str)) => {
+                                field_key = lit_str.value();
+                            }
+                            ("migrate_from", ir::AttrValue::LitStr(lit_str)) => {
+                                if schema_version.is_none() {
+                                    bail_span!(
+                                        marker.ident.span(),
+                                        "`migrate_from` requires the storage struct to \
+                                         be marked with `#[liquid(schema_version = \
+                                         ...)]`"
+                                    )
+                                }
+                                let old_ty = lit_str.parse::<syn::Type>()?;
+                                let container = ir_utils::container_name(&field.ty);
+                                match container.as_deref() {
+                                    Some("Value") | Some("Vec") => (),
+                                    _ => bail!(
+                                        field.ty,
+                                        "`migrate_from` is only supported for fields of \
+                                         type `storage::Value` or `storage::Vec`"
+                                    ),
+                                }
+                                if ir_utils::container_name(&old_ty) != container {
+                                    bail!(
+                                        lit_str,
+                                        "the old type should be a `storage::{}` too",
+                                        container.unwrap()
+                                    )
+                                }
+                                migrated_fields.push((i, old_ty));
+                            }
+                            ("rename_from", _) | ("migrate_from", _) => bail_span!(
+                                marker.ident.span(),
+                                "the value of `{}` should be a literal string",
+                                marker.ident
+                            ),
+                            _ => bail_span!(
+                                marker.ident.span(),
+                                "unknown liquid attribute for fields of \
+                                 `#[liquid(storage)]` struct, only `rename_from` and \
+                                 `migrate_from` are allowed"
+                            ),
+                        }
+                    }
+                    field
+                        .attrs
+                        .retain(|attr| !lang_utils::is_liquid_attribute(attr));
+
+                    if !unique_keys.insert(field_key.clone()) {
+                        bail!(
+                            field,
+                            "the storage key `{}` of this field is already used by \
+                             another field",
+                            field_key
+                        )
+                    }
+                    field_keys.push(field_key);
+                }
+
                 let fields = &named_fields.named;
                 for (i, field) in fields.iter().enumerate() {
                     let visibility = &field.vis;
@@ -686,6 +873,10 @@ impl TryFrom<syn::ItemStruct> for ir::ItemStorage {
             ident: item_struct.ident,
             fields,
             public_fields,
+            field_keys,
+            migrated_fields,
+            schema_version,
+            migration_guard,
             span,
         })
     }
@@ -870,6 +1061,14 @@ impl TryFrom<syn::Item> for ir::Item {
                 if markers.is_empty() {
                     return Ok(ir::Item::Rust(Box::new(item.into())));
                 }
+                // `liquid(schema_version = ...)` and `liquid(migration_guard = ...)`
+                // are options of the storage struct rather than kinds of liquid
+                // items.
+                let (options, markers): (Vec<_>, Vec<_>) =
+                    markers.into_iter().partition(|marker| {
+                        marker.ident == "schema_version"
+                            || marker.ident == "migration_guard"
+                    });
                 if markers.len() > 1 {
                     bail!(
                         item_struct,
@@ -878,6 +1077,15 @@ impl TryFrom<syn::Item> for ir::Item {
                          same time"
                     )
                 }
+                if let Some(option) = options.first() {
+                    if markers.is_empty() || markers[0].ident != "storage" {
+                        bail_span!(
+                            option.ident.span(),
+                            "`{}` can only be used along with `liquid(storage)`",
+                            option.ident
+                        )
+                    }
+                }
 
                 let marker = markers[0].ident.to_string();
                 match marker.as_str() {
@@ -1058,6 +1266,7 @@ impl TryFrom<(ir::InterfaceParams, syn::ItemMod)> for ir::Interface {
 
         let mut foreign_structs = Vec::new();
         let mut foreign_fns = BTreeMap::<_, ir::ForeignFn>::new();
+        let mut constructor = None;
         let mut imports = Vec::new();
         let span = item_mod.span();
 
@@ -1077,7 +1286,7 @@ impl TryFrom<(ir::InterfaceParams, syn::ItemMod)> for ir::Interface {
                     imports.push(item_use);
                 }
                 syn::Item::ForeignMod(item_foreign_mod) => {
-                    if !foreign_fns.is_empty() {
+                    if !foreign_fns.is_empty() || constructor.is_some() {
                         bail!(
                             item_foreign_mod,
                             "interface module must have exactly one `extern` block"
@@ -1099,7 +1308,36 @@ impl TryFrom<(ir::InterfaceParams, syn::ItemMod)> for ir::Interface {
                     for foreign_item in item_foreign_mod.items.iter() {
                         let foreign_fn = ir::ForeignFn::try_from(foreign_item)?;
                         let ident = foreign_fn.sig.ident.clone();
-                        if let Some(foreign_fn) = foreign_fns.get_mut(&ident) {
+                        if ident == "new" {
+                            if constructor.is_some() {
+                                bail_span!(
+                                    foreign_fn.span,
+                                    "duplicate constructor declaration found here"
+                                )
+                            }
+                            if !foreign_fn.sig.is_mut() {
+                                bail_span!(
+                                    foreign_fn.span,
+                                    "`&mut self` is mandatory first parameter for the \
+                                     constructor declaration"
+                                )
+                            }
+                            if let syn::ReturnType::Type(..) = foreign_fn.sig.output {
+                                bail!(
+                                    foreign_fn.sig.output,
+                                    "the constructor declaration should not return \
+                                     anything"
+                                )
+                            }
+                            if foreign_fn.mock_context_getter.is_some() {
+                                bail_span!(
+                                    foreign_fn.span,
+                                    "`mock_context_getter` is not allowed for the \
+                                     constructor declaration"
+                                )
+                            }
+                            constructor = Some(foreign_fn);
+                        } else if let Some(foreign_fn) = foreign_fns.get_mut(&ident) {
                             bail_span!(
                                 foreign_fn.span,
                                 "overriding methods is not supported in liquid"
@@ -1109,7 +1347,7 @@ impl TryFrom<(ir::InterfaceParams, syn::ItemMod)> for ir::Interface {
                         }
                     }
 
-                    if foreign_fns.is_empty() {
+                    if foreign_fns.is_empty() && constructor.is_none() {
                         bail!(
                             item_foreign_mod,
                             "at least one declaration of method should be provided in \
@@ -1125,12 +1363,43 @@ impl TryFrom<(ir::InterfaceParams, syn::ItemMod)> for ir::Interface {
             }
         }
 
+        // `deploy` and `deploy_with_salt` are generated for interfaces which
+        // declare the constructor.
+        if constructor.is_some() {
+            if let Some(foreign_fn) = foreign_fns.values().find(|foreign_fn| {
+                let ident = &foreign_fn.sig.ident;
+                ident == "deploy" || ident == "deploy_with_salt"
+            }) {
+                bail!(
+                    foreign_fn.sig.ident,
+                    "the name `{}` is reserved for deploying the contract when the \
+                     constructor is declared in interface",
+                    foreign_fn.sig.ident
+                )
+            }
+        }
+
+        // Every method is accompanied by a generated `try_` variant.
+        for foreign_fn in foreign_fns.values() {
+            let ident = &foreign_fn.sig.ident;
+            let try_ident = Ident::new(&format!("try_{}", ident), ident.span());
+            if let Some(try_fn) = foreign_fns.get(&try_ident) {
+                bail!(
+                    try_fn.sig.ident,
+                    "the name `{}` is reserved for the `try_` variant of `{}`",
+                    try_ident,
+                    ident
+                )
+            }
+        }
+
         Ok(Self {
             mod_token: item_mod.mod_token,
             ident: item_mod.ident,
             meta_info,
             foreign_structs,
             foreign_fns,
+            constructor,
             imports,
             interface_ident,
             span,
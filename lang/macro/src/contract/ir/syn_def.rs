@@ -78,6 +78,17 @@ pub struct ItemStorage {
     pub fields: syn::FieldsNamed,
     /// Public fields that need to generate a corresponding getter.
     pub public_fields: Vec<usize>,
+    /// Storage keys of fields, which are the names of fields unless the fields
+    /// are marked with `#[liquid(rename_from = "...")]`.
+    pub field_keys: Vec<String>,
+    /// Fields marked with `#[liquid(migrate_from = "...")]` along with their
+    /// old types, whose data need to be converted during the migration.
+    pub migrated_fields: Vec<(usize, syn::Type)>,
+    /// The schema version specified by `#[liquid(schema_version = ...)]`.
+    pub schema_version: Option<u32>,
+    /// The method specified by `#[liquid(migration_guard = "...")]`, which
+    /// returns whether the caller is allowed to migrate the storage.
+    pub migration_guard: Option<Ident>,
     /// Span of the storage struct.
     pub span: Span,
 }
@@ -368,6 +379,7 @@ impl ToTokens for IdentType {
 #[derive(Debug)]
 pub enum AttrValue {
     LitStr(syn::LitStr),
+    LitInt(syn::LitInt),
     Ident(syn::Ident),
     Fields(Vec<AssetAttribute>),
     None,
@@ -385,9 +397,14 @@ impl Parse for AttrValue {
             return Ok(Self::LitStr(lit_str));
         }
 
+        if input.peek(syn::LitInt) {
+            let lit_int = input.parse::<syn::LitInt>()?;
+            return Ok(Self::LitInt(lit_int));
+        }
+
         Err(input.error(
-            "invalid value of an liquid attribute, identifier or a literal string \
-             required",
+            "invalid value of an liquid attribute, identifier, a literal string or a \
+             literal integer required",
         ))
     }
 }
@@ -498,6 +515,9 @@ pub struct Interface {
     pub foreign_structs: Vec<ForeignStruct>,
     /// The declarations of methods.
     pub foreign_fns: BTreeMap<Ident, ForeignFn>,
+    /// The declaration of the constructor, i.e., `fn new(&mut self, ...)`,
+    /// which is used to deploy new instances of the foreign contract.
+    pub constructor: Option<ForeignFn>,
     /// The use declarations to import other symbols.
     pub imports: Vec<syn::ItemUse>,
     /// The name of auto-generated interface struct.
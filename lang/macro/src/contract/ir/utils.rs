@@ -100,3 +100,16 @@ pub fn split_items(items: Vec<LiquidItem>, span: Span) -> Result<ContractItems>
     let constants = constants.into_iter().flatten().collect();
     Ok((storage, events, assets, functions, constants))
 }
+
+/// Returns the name of the container in the type of a storage field, e.g.,
+/// `Vec` for `storage::Vec<u32>`.
+pub fn container_name(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
@@ -113,3 +113,5 @@ fn generate_impl(
 
 pub const SUPPORTS_ASSET_NAME: &str = "__liquid_supports_asset";
 pub const SUPPORTS_ASSET_SIGNATURE: &str = "__liquid_supports_asset(string)";
+pub const MIGRATE_NAME: &str = "__liquid_migrate";
+pub const MIGRATE_SIGNATURE: &str = "__liquid_migrate()";
@@ -84,6 +84,11 @@ fn generate_impl(input: TokenStream2) -> Result<TokenStream2> {
                 &field_tys,
                 ident,
             );
+            let arbitrary_impls = if cfg!(feature = "fuzz") {
+                generate_arbitrary_struct(ident, &field_names, &field_tys, is_unnamed)
+            } else {
+                quote! {}
+            };
 
             let mut field_checkers = Vec::new();
             for (i, ty) in field_tys.iter().enumerate() {
@@ -100,6 +105,7 @@ fn generate_impl(input: TokenStream2) -> Result<TokenStream2> {
                 #encode_shadow_struct
                 #decode_shadow_struct
                 #abi_impls
+                #arbitrary_impls
             }
         }
         Data::Enum(ref enum_data) => {
@@ -188,12 +194,18 @@ fn generate_impl(input: TokenStream2) -> Result<TokenStream2> {
             let encode_shadow_enum = generate_encode_shadow_enum(ident, variants.iter());
             let decode_shadow_enum = generate_decode_shadow_enum(ident, variants.iter());
             let abi_impls = generate_abi_enum(ident, variants.as_slice());
+            let arbitrary_impls = if cfg!(feature = "fuzz") {
+                generate_arbitrary_enum(ident, variants.as_slice())
+            } else {
+                quote! {}
+            };
 
             quote! {
                 #(#field_checkers)*
                 #encode_shadow_enum
                 #decode_shadow_enum
                 #abi_impls
+                #arbitrary_impls
             }
         }
     };
@@ -613,3 +625,178 @@ fn generate_abi_enum(ident: &Ident, variants: &[Variant]) -> TokenStream2 {
         }
     }
 }
+
+fn field_members(field_names: &[Ident], is_unnamed: bool) -> Vec<TokenStream2> {
+    field_names
+        .iter()
+        .enumerate()
+        .map(|(i, field_name)| {
+            if is_unnamed {
+                let index = syn::Index::from(i);
+                quote!(#index)
+            } else {
+                quote!(#field_name)
+            }
+        })
+        .collect()
+}
+
+fn generate_describe_fields(
+    name: &str,
+    field_names: &[Ident],
+    is_unnamed: bool,
+) -> TokenStream2 {
+    if is_unnamed {
+        quote! {
+            let mut fields = f.debug_tuple(#name);
+            #(fields.field(&liquid_lang::fuzz::Describe(#field_names));)*
+            fields.finish()
+        }
+    } else {
+        let names = field_names.iter().map(|field_name| field_name.to_string());
+        quote! {
+            let mut fields = f.debug_struct(#name);
+            #(fields.field(#names, &liquid_lang::fuzz::Describe(#field_names));)*
+            fields.finish()
+        }
+    }
+}
+
+fn generate_arbitrary_struct(
+    ident: &Ident,
+    field_names: &[Ident],
+    field_tys: &[&Type],
+    is_unnamed: bool,
+) -> TokenStream2 {
+    let members = field_members(field_names, is_unnamed);
+    let describe_fields =
+        generate_describe_fields(&ident.to_string(), field_names, is_unnamed);
+    let shrink_fields = field_names.iter().map(|field_name| {
+        let others = field_names
+            .iter()
+            .zip(members.iter())
+            .map(|(other, member)| {
+                if other == field_name {
+                    quote!(#member: candidate)
+                } else {
+                    quote!(#member: #other.clone())
+                }
+            });
+        quote! {
+            for candidate in liquid_lang::fuzz::Arbitrary::shrink(#field_name) {
+                shrunk.push(Self { #(#others,)* });
+            }
+        }
+    });
+
+    quote! {
+        #[cfg(test)]
+        impl liquid_lang::fuzz::Arbitrary for #ident {
+            fn arbitrary(rng: &mut liquid_lang::fuzz::Rng) -> Self {
+                Self {
+                    #(#members: <#field_tys as liquid_lang::fuzz::Arbitrary>::arbitrary(rng),)*
+                }
+            }
+
+            #[allow(unused_mut)]
+            fn shrink(&self) -> Vec<Self> {
+                let mut shrunk = Vec::new();
+                let Self { #(#members: #field_names,)* } = self;
+                #(#shrink_fields)*
+                shrunk
+            }
+
+            fn describe(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                let Self { #(#members: #field_names,)* } = self;
+                #describe_fields
+            }
+        }
+    }
+}
+
+fn generate_arbitrary_enum(ident: &Ident, variants: &[Variant]) -> TokenStream2 {
+    let variants_count = variants.len() as u64;
+    let mut arbitrary_arms = Vec::new();
+    let mut shrink_arms = Vec::new();
+    let mut describe_arms = Vec::new();
+
+    for (i, variant) in variants.iter().enumerate() {
+        let i = i as u64;
+        let variant_ident = variant.ident;
+        let field_names = &variant.field_names;
+        let field_tys = &variant.field_tys;
+        let members = field_members(field_names, variant.unnamed);
+
+        arbitrary_arms.push(quote! {
+            #i => Self::#variant_ident {
+                #(#members: <#field_tys as liquid_lang::fuzz::Arbitrary>::arbitrary(rng),)*
+            },
+        });
+
+        let shrink_fields = field_names.iter().map(|field_name| {
+            let others = field_names
+                .iter()
+                .zip(members.iter())
+                .map(|(other, member)| {
+                    if other == field_name {
+                        quote!(#member: candidate)
+                    } else {
+                        quote!(#member: #other.clone())
+                    }
+                });
+            quote! {
+                for candidate in liquid_lang::fuzz::Arbitrary::shrink(#field_name) {
+                    shrunk.push(Self::#variant_ident { #(#others,)* });
+                }
+            }
+        });
+        shrink_arms.push(quote! {
+            Self::#variant_ident { #(#members: #field_names,)* } => {
+                #(#shrink_fields)*
+            }
+        });
+
+        let describe_fields = if variant.is_unit {
+            let name = variant_ident.to_string();
+            quote!(f.write_str(#name))
+        } else {
+            generate_describe_fields(
+                &variant_ident.to_string(),
+                field_names,
+                variant.unnamed,
+            )
+        };
+        describe_arms.push(quote! {
+            Self::#variant_ident { #(#members: #field_names,)* } => {
+                #describe_fields
+            }
+        });
+    }
+
+    quote! {
+        #[cfg(test)]
+        impl liquid_lang::fuzz::Arbitrary for #ident {
+            fn arbitrary(rng: &mut liquid_lang::fuzz::Rng) -> Self {
+                match rng.below(#variants_count) {
+                    #(#arbitrary_arms)*
+                    _ => unreachable!(),
+                }
+            }
+
+            #[allow(unused_mut)]
+            fn shrink(&self) -> Vec<Self> {
+                let mut shrunk = Vec::new();
+                match self {
+                    #(#shrink_arms)*
+                }
+                shrunk
+            }
+
+            fn describe(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                match self {
+                    #(#describe_arms)*
+                }
+            }
+        }
+    }
+}
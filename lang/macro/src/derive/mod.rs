@@ -15,4 +15,5 @@ extern crate proc_macro;
 #[macro_use]
 mod error;
 pub mod inout;
+pub mod storage_layout;
 pub mod wrapper;
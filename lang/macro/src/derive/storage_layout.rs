@@ -0,0 +1,214 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use liquid_prelude::vec::Vec;
+use proc_macro2::{Ident, Literal, Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+use syn::{self, parse::Result, spanned::Spanned, Data, DeriveInput, Fields, Type};
+
+pub fn generate(input: TokenStream2) -> TokenStream2 {
+    match generate_impl(input) {
+        Ok(output) => output,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+fn generate_impl(input: TokenStream2) -> Result<TokenStream2> {
+    let ast: DeriveInput = syn::parse2(input)?;
+
+    match &ast.vis {
+        syn::Visibility::Public(_) => (),
+        _ => bail!(ast, "the visibility of this type should be `pub`"),
+    }
+
+    if ast.generics.type_params().count() > 0 {
+        bail!(&ast.generics, "generic are not supported")
+    }
+
+    let ident = &ast.ident;
+    let fields = match &ast.data {
+        Data::Union(ref union_data) => {
+            bail!(&union_data.union_token, "unions are not supported")
+        }
+        Data::Enum(ref enum_data) => {
+            bail!(&enum_data.enum_token, "enums are not supported")
+        }
+        Data::Struct(ref struct_data) => &struct_data.fields,
+    };
+
+    // Each field is bound with the key of the struct followed by `$` and the
+    // name (or the index for tuple structs) of the field.
+    let (members, field_keys, field_tys): (Vec<_>, Vec<_>, Vec<&Type>) = match fields {
+        Fields::Named(fields_named) => fields_named
+            .named
+            .iter()
+            .map(|field| {
+                let field_ident = field.ident.as_ref().unwrap();
+                let field_key = field_ident.to_string();
+                (quote!(#field_ident), field_key, &field.ty)
+            })
+            .fold((Vec::new(), Vec::new(), Vec::new()), unzip3),
+        Fields::Unnamed(fields_unnamed) => fields_unnamed
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let index = syn::Index::from(i);
+                (quote!(#index), i.to_string(), &field.ty)
+            })
+            .fold((Vec::new(), Vec::new(), Vec::new()), unzip3),
+        Fields::Unit => (Vec::new(), Vec::new(), Vec::new()),
+    };
+
+    let mut field_checkers = Vec::new();
+    for (i, ty) in field_tys.iter().enumerate() {
+        let field_checker =
+            Ident::new(&format!("__LIQUID_FIELD_CHECKER_{i}"), Span::call_site());
+        let state_ty_guards = if cfg!(feature = "contract") {
+            quote_spanned! { ty.span() =>
+                <<#ty as liquid_lang::storage::You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage>::Wrapped1 as liquid_lang::You_Should_Use_An_Valid_State_Type>::T,
+                <<#ty as liquid_lang::storage::You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage>::Wrapped2 as liquid_lang::You_Should_Use_An_Valid_State_Type>::T,
+            }
+        } else {
+            quote! {}
+        };
+        field_checkers.push(quote_spanned! { ty.span() =>
+            #[allow(non_camel_case_types)]
+            struct #field_checker(<#ty as liquid_lang::storage::You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage>::T, #state_ty_guards);
+        });
+    }
+
+    let field_key_lits = field_keys
+        .iter()
+        .map(|field_key| Literal::byte_string(field_key.as_bytes()))
+        .collect::<Vec<_>>();
+    let bind_stats = |bind: Ident| {
+        members
+            .iter()
+            .zip(field_tys.iter())
+            .zip(field_key_lits.iter())
+            .map(|((member, ty), field_key)| {
+                quote! {
+                    #member: <#ty as liquid_lang::storage::Bind>::#bind(&field_key(#field_key)),
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let bind_with_stats = bind_stats(Ident::new("bind_with", Span::call_site()));
+    // Fields of a struct nested in another container are nested as well.
+    let bind_nested_stats = bind_stats(Ident::new("bind_nested", Span::call_site()));
+    let field_key = quote! {
+        let field_key = |field: &[u8]| {
+            let mut field_key = Vec::with_capacity(key.len() + 1 + field.len());
+            field_key.extend_from_slice(key);
+            field_key.push(b'$');
+            field_key.extend_from_slice(field);
+            field_key
+        };
+    };
+
+    let describe_fields = match fields {
+        Fields::Unnamed(_) => {
+            let name = ident.to_string();
+            quote! {
+                let mut fields = f.debug_tuple(#name);
+                #(fields.field(&self.#members);)*
+                fields.finish()
+            }
+        }
+        _ => {
+            let name = ident.to_string();
+            quote! {
+                let mut fields = f.debug_struct(#name);
+                #(fields.field(#field_keys, &self.#members);)*
+                fields.finish()
+            }
+        }
+    };
+
+    let mut impls = quote! {
+        #(#field_checkers)*
+
+        impl liquid_lang::storage::Bind for #ident {
+            #[allow(unused_variables)]
+            fn bind_with(key: &[u8]) -> Self {
+                #field_key
+                Self {
+                    #(#bind_with_stats)*
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn bind_nested(key: &[u8]) -> Self {
+                #field_key
+                Self {
+                    #(#bind_nested_stats)*
+                }
+            }
+        }
+
+        impl liquid_lang::storage::Flush for #ident {
+            fn flush(&mut self) {
+                #(liquid_lang::storage::Flush::flush(&mut self.#members);)*
+            }
+        }
+
+        impl liquid_lang::storage::Storable for #ident {
+            fn get_in<'a>(
+                chunk: &'a liquid_lang::storage::CachedChunk<Self>,
+                index: &[u8],
+            ) -> Option<&'a Self> {
+                Some(chunk.bind(index))
+            }
+
+            fn get_mut_in<'a>(
+                chunk: &'a mut liquid_lang::storage::CachedChunk<Self>,
+                index: &[u8],
+            ) -> Option<&'a mut Self> {
+                Some(chunk.bind_mut(index))
+            }
+
+            fn flush_in(chunk: &mut liquid_lang::storage::CachedChunk<Self>) {
+                chunk.flush_bound();
+            }
+        }
+
+        impl liquid_lang::storage::You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage for #ident {
+            type Wrapped1 = ();
+            type Wrapped2 = ();
+        }
+
+        #[cfg(feature = "std")]
+        impl core::fmt::Debug for #ident {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                #describe_fields
+            }
+        }
+    };
+
+    if cfg!(feature = "contract") {
+        impls.extend(quote! {
+            impl liquid_lang::You_Should_Use_An_Valid_State_Type for #ident {}
+        });
+    }
+    Ok(impls)
+}
+
+fn unzip3<A, B, C>(
+    (mut a, mut b, mut c): (Vec<A>, Vec<B>, Vec<C>),
+    (x, y, z): (A, B, C),
+) -> (Vec<A>, Vec<B>, Vec<C>) {
+    a.push(x);
+    b.push(y);
+    c.push(z);
+    (a, b, c)
+}
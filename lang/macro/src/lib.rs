@@ -60,9 +60,18 @@ cfg_if! {
     }
 }
 
-use derive::inout;
+use derive::{inout, storage_layout};
 
 #[proc_macro_derive(InOut)]
 pub fn inout_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     wrapper::generate_wrapper(inout::generate(input.into())).into()
 }
+
+/// Derives storage layout for a struct whose fields are all storage
+/// containers. Each field is bound with the key of the struct followed by `$`
+/// and the name of the field, so that the struct can be used as a state field
+/// or as the value of a `Mapping`, and only the accessed fields are loaded.
+#[proc_macro_derive(StorageLayout)]
+pub fn storage_layout_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    wrapper::generate_wrapper(storage_layout::generate(input.into())).into()
+}
@@ -11,6 +11,10 @@
 // limitations under the License.
 
 use crate::lang_core::env::api;
+#[cfg(any(feature = "std", feature = "unstable-host-api"))]
+use liquid_prelude::string::String;
+#[cfg(any(feature = "std", feature = "unstable-host-api"))]
+use liquid_primitives::types::Hash;
 use liquid_primitives::types::{timestamp, Address};
 
 pub struct EnvAccess;
@@ -36,6 +40,31 @@ impl EnvAccess {
         api::get_block_number()
     }
 
+    #[cfg(any(feature = "std", feature = "unstable-host-api"))]
+    pub fn get_tx_hash(self) -> Hash {
+        api::get_tx_hash()
+    }
+
+    #[cfg(any(feature = "std", feature = "unstable-host-api"))]
+    pub fn get_block_hash(self, block_number: u64) -> Option<Hash> {
+        api::get_block_hash(block_number)
+    }
+
+    #[cfg(any(feature = "std", feature = "unstable-host-api"))]
+    pub fn get_chain_id(self) -> String {
+        api::get_chain_id()
+    }
+
+    #[cfg(any(feature = "std", feature = "unstable-host-api"))]
+    pub fn get_group_id(self) -> String {
+        api::get_group_id()
+    }
+
+    #[cfg(any(feature = "std", feature = "unstable-host-api"))]
+    pub fn get_gas_left(self) -> u64 {
+        api::get_gas_left()
+    }
+
     #[allow(clippy::wrong_self_convention)]
     pub fn is_contract(self, account: &Address) -> bool {
         matches!(api::get_external_code_size(account), 0)
@@ -0,0 +1,992 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deterministic fuzzing of contract methods in the off-chain environment.
+//!
+//! When the `fuzz` feature is enabled, `#[liquid::contract]` generates an
+//! implementation of [`Fuzzable`] for the `TestableStorage` of the contract,
+//! and `#[derive(InOut)]` generates an implementation of [`Arbitrary`] for the
+//! derived type. A [`Harness`] then executes random sequences of calls to the
+//! external methods which take `&mut self`, with random arguments and callers,
+//! and checks the declared invariants after every call:
+//!
+//! ```ignore
+//! #[test]
+//! fn total_supply_is_conserved() {
+//!     liquid_lang::fuzz::Harness::new(|| TestableStorage::new(1000))
+//!         .invariant("sum of balances", |contract| {
+//!             let accounts = test::default_accounts();
+//!             let sum: u128 = [accounts.alice, accounts.bob, accounts.charlie]
+//!                 .iter()
+//!                 .map(|owner| contract.balance_of(owner.clone()))
+//!                 .sum();
+//!             sum == contract.total_supply()
+//!         })
+//!         .run();
+//! }
+//! ```
+//!
+//! Every step runs like a transaction: the contract is loaded from the test
+//! environment, the method is invoked and the contract is flushed back, and
+//! all these changes are rolled back if the method reverts. Reverts are
+//! expected and tolerated, while a panic which is not caused by reverting, or
+//! a violated invariant, fails the harness. The failing sequence is shrunk to
+//! a minimal one, and is reported together with the seed to replay it, either
+//! via [`Harness::seed`] or the `LIQUID_FUZZ_SEED` environment variable.
+
+use crate::env::test;
+use liquid_primitives::types::*;
+use std::{
+    any::Any,
+    cell::Cell,
+    fmt::{self, Write},
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+/// The environment variable to override the seed of the first run.
+pub const SEED_ENV: &str = "LIQUID_FUZZ_SEED";
+/// The environment variable to override the number of runs.
+pub const RUNS_ENV: &str = "LIQUID_FUZZ_RUNS";
+
+const DEFAULT_SEED: u64 = 0x6c69_7175_6964;
+const DEFAULT_RUNS: u32 = 64;
+const DEFAULT_STEPS: u32 = 32;
+const DEFAULT_SHRINKS: u32 = 512;
+const MAX_COLLECTION_LEN: u64 = 4;
+const MAX_STRING_LEN: u64 = 8;
+
+/// A small deterministic pseudo random number generator (SplitMix64).
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_u128(&mut self) -> u128 {
+        ((self.next_u64() as u128) << 64) | self.next_u64() as u128
+    }
+
+    /// Returns a number in range `[0, n)`.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "the upper bound must be positive");
+        self.next_u64() % n
+    }
+
+    /// Returns `true` with probability `num / den`.
+    pub fn ratio(&mut self, num: u64, den: u64) -> bool {
+        self.below(den) < num
+    }
+}
+
+/// Values which can be generated randomly and shrunk to simpler ones.
+///
+/// Implementors are not required to be `Clone`, only the implementations whose
+/// shrinking copies the value, e.g., the ones for collections and tuples,
+/// require their elements to be `Clone`.
+pub trait Arbitrary {
+    /// Generates a random value.
+    fn arbitrary(rng: &mut Rng) -> Self;
+
+    /// Returns simpler candidates of this value, which are tried in order when
+    /// minimizing a failing sequence.
+    fn shrink(&self) -> Vec<Self> {
+        Vec::new()
+    }
+
+    /// Formats this value for the report of a failing sequence.
+    fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+/// Formats the wrapped value with [`Arbitrary::describe`].
+pub struct Describe<'a, T>(pub &'a T);
+
+impl<'a, T: Arbitrary> fmt::Debug for Describe<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.describe(f)
+    }
+}
+
+macro_rules! impl_arbitrary_for_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Arbitrary for $t {
+                fn arbitrary(rng: &mut Rng) -> Self {
+                    match rng.below(4) {
+                        0 => [0, 1, <$t>::MAX - 1, <$t>::MAX][rng.below(4) as usize],
+                        1 => rng.below(32) as $t,
+                        _ => rng.next_u128() as $t,
+                    }
+                }
+
+                fn shrink(&self) -> Vec<Self> {
+                    let mut shrunk = Vec::new();
+                    for candidate in [0, *self / 2, self.saturating_sub(1)] {
+                        if candidate != *self && !shrunk.contains(&candidate) {
+                            shrunk.push(candidate);
+                        }
+                    }
+                    shrunk
+                }
+
+                fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    fmt::Debug::fmt(self, f)
+                }
+            }
+        )*
+    };
+}
+
+impl_arbitrary_for_unsigned!(u8, u16, u32, u64, u128);
+
+macro_rules! impl_arbitrary_for_signed {
+    ($($t:ty),*) => {
+        $(
+            impl Arbitrary for $t {
+                fn arbitrary(rng: &mut Rng) -> Self {
+                    match rng.below(4) {
+                        0 => [0, 1, -1, <$t>::MIN, <$t>::MAX][rng.below(5) as usize],
+                        1 => rng.below(32) as $t - 16,
+                        _ => rng.next_u128() as $t,
+                    }
+                }
+
+                fn shrink(&self) -> Vec<Self> {
+                    let mut shrunk = Vec::new();
+                    let towards_zero = *self - self.signum();
+                    for candidate in [0, self.saturating_abs(), *self / 2, towards_zero] {
+                        if candidate != *self && !shrunk.contains(&candidate) {
+                            shrunk.push(candidate);
+                        }
+                    }
+                    shrunk
+                }
+
+                fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    fmt::Debug::fmt(self, f)
+                }
+            }
+        )*
+    };
+}
+
+impl_arbitrary_for_signed!(i8, i16, i32, i64, i128);
+
+impl Arbitrary for u256 {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        u256::from(u128::arbitrary(rng))
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let zero = u256::from(0u8);
+        if *self != zero {
+            vec![zero]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Arbitrary for i256 {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        i256::from(i128::arbitrary(rng))
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let zero = i256::from(0i8);
+        if *self != zero {
+            vec![zero]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Arbitrary for bool {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        rng.ratio(1, 2)
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        if *self {
+            vec![false]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl Arbitrary for () {
+    fn arbitrary(_: &mut Rng) -> Self {}
+
+    fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("()")
+    }
+}
+
+impl Arbitrary for String {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        let len = rng.below(MAX_STRING_LEN + 1);
+        (0..len)
+            .map(|_| (b'a' + rng.below(26) as u8) as char)
+            .collect()
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+
+        let mut shrunk = vec![String::new()];
+        if self.chars().count() > 1 {
+            let mut truncated = self.clone();
+            truncated.pop();
+            shrunk.push(truncated);
+        }
+        shrunk
+    }
+
+    fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Addresses are chosen from the default accounts, so that they are likely to
+/// be the callers of other steps.
+impl Arbitrary for Address {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        let accounts = accounts();
+        accounts[rng.below(accounts.len() as u64) as usize].clone()
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let alice = test::default_accounts().alice;
+        if *self != alice {
+            vec![alice]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Arbitrary for Bytes {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        Bytes::from(Vec::<u8>::arbitrary(rng))
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        self.to_vec()
+            .shrink()
+            .into_iter()
+            .map(Bytes::from)
+            .collect()
+    }
+
+    fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "0x{}", crate::bytes_to_hex(self))
+    }
+}
+
+liquid_macro::seq!(N in 1..=32 {
+    #(
+        impl Arbitrary for Bytes#N {
+            fn arbitrary(rng: &mut Rng) -> Self {
+                Self(<[u8; N]>::arbitrary(rng))
+            }
+
+            fn shrink(&self) -> Vec<Self> {
+                if *self != Self::default() {
+                    vec![Self::default()]
+                } else {
+                    Vec::new()
+                }
+            }
+
+            fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "0x{}", crate::bytes_to_hex(&self.0))
+            }
+        }
+    )*
+});
+
+impl<T: Arbitrary + Clone> Arbitrary for Vec<T> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        let len = rng.below(MAX_COLLECTION_LEN + 1);
+        (0..len).map(|_| T::arbitrary(rng)).collect()
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+
+        let mut shrunk = vec![Vec::new()];
+        if self.len() > 1 {
+            for i in 0..self.len() {
+                let mut removed = self.clone();
+                removed.remove(i);
+                shrunk.push(removed);
+            }
+        }
+        for (i, elem) in self.iter().enumerate() {
+            for candidate in elem.shrink() {
+                let mut replaced = self.clone();
+                replaced[i] = candidate;
+                shrunk.push(replaced);
+            }
+        }
+        shrunk
+    }
+
+    fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter().map(Describe)).finish()
+    }
+}
+
+impl<T: Arbitrary + Clone, const N: usize> Arbitrary for [T; N] {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        core::array::from_fn(|_| T::arbitrary(rng))
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut shrunk = Vec::new();
+        for (i, elem) in self.iter().enumerate() {
+            for candidate in elem.shrink() {
+                let mut replaced = self.clone();
+                replaced[i] = candidate;
+                shrunk.push(replaced);
+            }
+        }
+        shrunk
+    }
+
+    fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter().map(Describe)).finish()
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Option<T> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        if rng.ratio(1, 4) {
+            None
+        } else {
+            Some(T::arbitrary(rng))
+        }
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        match self {
+            None => Vec::new(),
+            Some(value) => core::iter::once(None)
+                .chain(value.shrink().into_iter().map(Some))
+                .collect(),
+        }
+    }
+
+    fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            None => f.write_str("None"),
+            Some(value) => f.debug_tuple("Some").field(&Describe(value)).finish(),
+        }
+    }
+}
+
+macro_rules! impl_arbitrary_for_tuple {
+    ($(($($t:ident: $i:tt),+))*) => {
+        $(
+            impl<$($t: Arbitrary + Clone),+> Arbitrary for ($($t,)+) {
+                fn arbitrary(rng: &mut Rng) -> Self {
+                    ($($t::arbitrary(rng),)+)
+                }
+
+                fn shrink(&self) -> Vec<Self> {
+                    let mut shrunk = Vec::new();
+                    $(
+                        for candidate in self.$i.shrink() {
+                            let mut replaced = self.clone();
+                            replaced.$i = candidate;
+                            shrunk.push(replaced);
+                        }
+                    )+
+                    shrunk
+                }
+
+                fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    let mut tuple = f.debug_tuple("");
+                    $(tuple.field(&Describe(&self.$i));)+
+                    tuple.finish()
+                }
+            }
+        )*
+    };
+}
+
+impl_arbitrary_for_tuple! {
+    (A: 0)
+    (A: 0, B: 1)
+    (A: 0, B: 1, C: 2)
+    (A: 0, B: 1, C: 2, D: 3)
+    (A: 0, B: 1, C: 2, D: 3, E: 4)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7)
+}
+
+fn accounts() -> [Address; 6] {
+    let accounts = test::default_accounts();
+    [
+        accounts.alice,
+        accounts.bob,
+        accounts.charlie,
+        accounts.david,
+        accounts.eve,
+        accounts.frank,
+    ]
+}
+
+/// Contracts whose state-changing methods can be fuzzed.
+///
+/// This trait is implemented by the `TestableStorage` of a contract when the
+/// `fuzz` feature is enabled, in which `Call` enumerates all external methods
+/// taking `&mut self`.
+pub trait Fuzzable: Sized {
+    type Call: Arbitrary + Clone;
+
+    /// Loads the contract from the test environment.
+    fn load() -> Self;
+
+    /// Writes the cached state of the contract to the test environment.
+    fn flush(&mut self);
+
+    /// Invokes the method described by `call`.
+    fn apply(&mut self, call: &Self::Call);
+}
+
+/// A single call in a fuzzed sequence.
+#[derive(Clone)]
+pub struct Step<C> {
+    pub caller: Address,
+    pub call: C,
+}
+
+impl<C: Arbitrary + Clone> Arbitrary for Step<C> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        Self {
+            caller: Address::arbitrary(rng),
+            call: C::arbitrary(rng),
+        }
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let callers = self.caller.shrink().into_iter().map(|caller| Self {
+            caller,
+            call: self.call.clone(),
+        });
+        let calls = self.call.shrink().into_iter().map(|call| Self {
+            caller: self.caller.clone(),
+            call,
+        });
+        callers.chain(calls).collect()
+    }
+
+    fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: ", self.caller.as_str())?;
+        self.call.describe(f)
+    }
+}
+
+/// The statistics of a successful fuzzing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    /// The number of executed sequences.
+    pub runs: u32,
+    /// The number of executed calls.
+    pub calls: u64,
+    /// The number of calls which reverted.
+    pub reverted: u64,
+}
+
+struct Failure {
+    /// Index of the failing step, or `None` if the deployment fails.
+    step: Option<usize>,
+    reason: String,
+}
+
+type Invariant<C> = (String, Box<dyn Fn(&C) -> bool>);
+
+/// Executes random call sequences against a contract and checks invariants.
+pub struct Harness<C: Fuzzable> {
+    deploy: Box<dyn Fn() -> C>,
+    invariants: Vec<Invariant<C>>,
+    seed: u64,
+    runs: u32,
+    steps: u32,
+    shrinks: u32,
+}
+
+impl<C: Fuzzable> Harness<C> {
+    /// Creates a harness in which each sequence starts with the contract
+    /// returned by `deploy`.
+    ///
+    /// # Note
+    ///
+    /// The test environment is reset before every sequence, so any contract
+    /// registrations or assets which the contract relies on should be set up
+    /// in `deploy`.
+    pub fn new<F>(deploy: F) -> Self
+    where
+        F: Fn() -> C + 'static,
+    {
+        Self {
+            deploy: Box::new(deploy),
+            invariants: Vec::new(),
+            seed: DEFAULT_SEED,
+            runs: DEFAULT_RUNS,
+            steps: DEFAULT_STEPS,
+            shrinks: DEFAULT_SHRINKS,
+        }
+    }
+
+    /// Declares an invariant which must hold after deployment and after every
+    /// call. A panic in `check` is regarded as a violation too.
+    pub fn invariant<F>(mut self, name: &str, check: F) -> Self
+    where
+        F: Fn(&C) -> bool + 'static,
+    {
+        self.invariants.push((name.to_owned(), Box::new(check)));
+        self
+    }
+
+    /// Sets the seed of the first run, and the seeds of following runs are
+    /// derived from it. `LIQUID_FUZZ_SEED` takes precedence over this seed.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the number of sequences to execute. `LIQUID_FUZZ_RUNS` takes
+    /// precedence over this number.
+    pub fn runs(mut self, runs: u32) -> Self {
+        self.runs = runs;
+        self
+    }
+
+    /// Sets the maximum length of a sequence.
+    pub fn steps(mut self, steps: u32) -> Self {
+        self.steps = steps.max(1);
+        self
+    }
+
+    /// Sets the maximum number of attempts to shrink a failing sequence.
+    pub fn shrinks(mut self, shrinks: u32) -> Self {
+        self.shrinks = shrinks;
+        self
+    }
+
+    /// Executes the sequences, and returns the statistics if no failure is
+    /// found.
+    ///
+    /// # Panics
+    ///
+    /// With a report of the minimal failing sequence and its seed.
+    pub fn run(self) -> Report {
+        let seed = env_var(SEED_ENV).unwrap_or(self.seed);
+        let runs = env_var(RUNS_ENV).map_or(self.runs, |runs| runs as u32);
+
+        let mut report = Report::default();
+        for run in 0..runs {
+            let run_seed = seed.wrapping_add(u64::from(run).wrapping_mul(0x9e37_79b9));
+            let sequence = self.generate(run_seed);
+            let outcome = silently(|| self.execute(&sequence, &mut report));
+            report.runs += 1;
+
+            if let Some(failure) = outcome {
+                let (sequence, failure) = silently(|| self.shrink(sequence, failure));
+                panic!(
+                    "{}",
+                    Self::failure_message(run, run_seed, &sequence, &failure)
+                );
+            }
+        }
+        report
+    }
+
+    fn generate(&self, seed: u64) -> Vec<Step<C::Call>> {
+        let mut rng = Rng::new(seed);
+        let len = rng.below(u64::from(self.steps)) + 1;
+        (0..len).map(|_| Step::arbitrary(&mut rng)).collect()
+    }
+
+    /// Executes `sequence` from a fresh environment, and returns the first
+    /// failure encountered.
+    fn execute(
+        &self,
+        sequence: &[Step<C::Call>],
+        report: &mut Report,
+    ) -> Option<Failure> {
+        test::reset();
+        if let Err(payload) =
+            panic::catch_unwind(AssertUnwindSafe(|| (self.deploy)().flush()))
+        {
+            return Some(Failure {
+                step: None,
+                reason: format!("deployment panicked: {}", panic_message(&*payload)),
+            });
+        }
+        if let Some(reason) = self.check_invariants() {
+            return Some(Failure { step: None, reason });
+        }
+
+        for (i, step) in sequence.iter().enumerate() {
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+                test::catch_revert(|| {
+                    let _guard = test::as_caller(step.caller.clone());
+                    let mut contract = C::load();
+                    contract.apply(&step.call);
+                    contract.flush();
+                })
+            }));
+
+            report.calls += 1;
+            match outcome {
+                Ok(Ok(())) => (),
+                Ok(Err(_)) => report.reverted += 1,
+                Err(payload) => {
+                    return Some(Failure {
+                        step: Some(i),
+                        reason: format!("panicked: {}", panic_message(&*payload)),
+                    })
+                }
+            }
+
+            if let Some(reason) = self.check_invariants() {
+                return Some(Failure {
+                    step: Some(i),
+                    reason,
+                });
+            }
+        }
+        None
+    }
+
+    fn check_invariants(&self) -> Option<String> {
+        let contract = C::load();
+        self.invariants.iter().find_map(|(name, check)| {
+            match panic::catch_unwind(AssertUnwindSafe(|| check(&contract))) {
+                Ok(true) => None,
+                Ok(false) => Some(format!("invariant `{name}` violated")),
+                Err(payload) => Some(format!(
+                    "invariant `{}` panicked: {}",
+                    name,
+                    panic_message(&*payload)
+                )),
+            }
+        })
+    }
+
+    /// Minimizes a failing sequence by removing steps and shrinking the
+    /// callers and arguments of the remaining ones.
+    fn shrink(
+        &self,
+        mut sequence: Vec<Step<C::Call>>,
+        mut failure: Failure,
+    ) -> (Vec<Step<C::Call>>, Failure) {
+        let mut attempts = 0;
+        let mut try_candidate = |candidate: Vec<Step<C::Call>>,
+                                 sequence: &mut Vec<Step<C::Call>>,
+                                 failure: &mut Failure| {
+            if attempts >= self.shrinks {
+                return false;
+            }
+            attempts += 1;
+
+            match self.execute(&candidate, &mut Report::default()) {
+                Some(found) => {
+                    let len = found.step.map_or(0, |step| step + 1);
+                    *sequence = candidate;
+                    sequence.truncate(len);
+                    *failure = found;
+                    true
+                }
+                None => false,
+            }
+        };
+
+        sequence.truncate(failure.step.map_or(0, |step| step + 1));
+        'shrinking: loop {
+            for i in (0..sequence.len()).rev() {
+                let mut candidate = sequence.clone();
+                candidate.remove(i);
+                if try_candidate(candidate, &mut sequence, &mut failure) {
+                    continue 'shrinking;
+                }
+            }
+
+            for i in 0..sequence.len() {
+                for shrunk in sequence[i].shrink() {
+                    let mut candidate = sequence.clone();
+                    candidate[i] = shrunk;
+                    if try_candidate(candidate, &mut sequence, &mut failure) {
+                        continue 'shrinking;
+                    }
+                }
+            }
+
+            break;
+        }
+        (sequence, failure)
+    }
+
+    fn failure_message(
+        run: u32,
+        seed: u64,
+        sequence: &[Step<C::Call>],
+        failure: &Failure,
+    ) -> String {
+        let mut message = match failure.step {
+            Some(step) => format!(
+                "fuzzing failed in run {} at step #{}: {}\n",
+                run, step, failure.reason
+            ),
+            None => format!(
+                "fuzzing failed in run {} after deployment: {}\n",
+                run, failure.reason
+            ),
+        };
+        let _ = writeln!(message, "minimal failing sequence:");
+        for (i, step) in sequence.iter().enumerate() {
+            let _ = writeln!(message, "    #{}  {:?}", i, Describe(step));
+        }
+        let _ = write!(
+            message,
+            "replay with `{SEED_ENV}={seed:#x}` or `Harness::seed({seed:#x})`"
+        );
+        message
+    }
+}
+
+fn env_var(name: &str) -> Option<u64> {
+    let value = std::env::var(name).ok()?;
+    let value = value.trim();
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    Some(parsed.unwrap_or_else(|_| panic!("invalid value of `{name}`: {value}")))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        (*msg).to_owned()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        String::from("<unknown>")
+    }
+}
+
+thread_local! {
+    static SILENT: Cell<bool> = Cell::new(false);
+}
+
+/// Runs `f` without printing the messages of panics raised by current thread,
+/// since most of them are expected reverts.
+fn silently<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    static INSTALL_HOOK: Once = Once::new();
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !SILENT.with(|silent| silent.get()) {
+                default_hook(info);
+            }
+        }));
+    });
+
+    let prev = SILENT.with(|silent| silent.replace(true));
+    let ret = panic::catch_unwind(AssertUnwindSafe(f));
+    SILENT.with(|silent| silent.set(prev));
+    ret.unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env;
+
+    const COUNTER_KEY: &[u8] = b"counter";
+
+    #[derive(Clone)]
+    enum CounterCall {
+        Add(u8),
+        Reset,
+    }
+
+    impl Arbitrary for CounterCall {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            if rng.ratio(1, 8) {
+                CounterCall::Reset
+            } else {
+                CounterCall::Add(u8::arbitrary(rng))
+            }
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            match self {
+                CounterCall::Add(n) => {
+                    n.shrink().into_iter().map(CounterCall::Add).collect()
+                }
+                CounterCall::Reset => Vec::new(),
+            }
+        }
+
+        fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                CounterCall::Add(n) => write!(f, "add({n})"),
+                CounterCall::Reset => f.write_str("reset()"),
+            }
+        }
+    }
+
+    struct Counter(u32);
+
+    impl Fuzzable for Counter {
+        type Call = CounterCall;
+
+        fn load() -> Self {
+            Counter(env::api::get_storage::<u32>(COUNTER_KEY).unwrap_or_default())
+        }
+
+        fn flush(&mut self) {
+            env::api::set_storage(COUNTER_KEY, &self.0);
+        }
+
+        fn apply(&mut self, call: &CounterCall) {
+            match call {
+                CounterCall::Add(n) => {
+                    if *n == 0 {
+                        env::revert(&String::from("zero"));
+                    }
+                    self.0 += u32::from(*n);
+                }
+                CounterCall::Reset => self.0 = 0,
+            }
+        }
+    }
+
+    #[test]
+    fn rng_is_deterministic() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..16 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn non_clone_values_shrink() {
+        struct Amount(u8);
+
+        impl Arbitrary for Amount {
+            fn arbitrary(rng: &mut Rng) -> Self {
+                Amount(u8::arbitrary(rng))
+            }
+
+            fn shrink(&self) -> Vec<Self> {
+                self.0.shrink().into_iter().map(Amount).collect()
+            }
+
+            fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        let shrunk = Some(Amount(10)).shrink();
+        assert!(shrunk[0].is_none());
+        assert!(shrunk[1..]
+            .iter()
+            .all(|amount| matches!(amount, Some(Amount(n)) if *n < 10)));
+    }
+
+    #[test]
+    fn passing_invariant() {
+        let report = Harness::new(|| Counter(0))
+            .invariant("bounded", |counter| counter.0 <= 255 * DEFAULT_STEPS)
+            .runs(16)
+            .run();
+        assert_eq!(report.runs, 16);
+        assert!(report.calls >= 16);
+    }
+
+    #[test]
+    fn failing_sequence_is_shrunk_and_replayable() {
+        let harness = || {
+            Harness::new(|| Counter(0))
+                .invariant("below 300", |counter| counter.0 < 300)
+                .seed(7)
+        };
+
+        let message = panic::catch_unwind(AssertUnwindSafe(|| harness().run()))
+            .map(|_| String::new())
+            .unwrap_or_else(|payload| panic_message(&*payload));
+        assert!(message.contains("invariant `below 300` violated"));
+        assert!(message.contains("alice: add("));
+        assert!(!message.contains("reset()"));
+
+        let seed = message
+            .rsplit("Harness::seed(")
+            .next()
+            .and_then(|rest| rest.strip_suffix(")`"))
+            .and_then(|hex| u64::from_str_radix(&hex[2..], 16).ok())
+            .unwrap();
+        let replayed =
+            panic::catch_unwind(AssertUnwindSafe(|| harness().seed(seed).runs(1).run()))
+                .map(|_| String::new())
+                .unwrap_or_else(|payload| panic_message(&*payload));
+        assert!(replayed.contains("fuzzing failed in run 0"));
+    }
+}
@@ -11,7 +11,7 @@
 // limitations under the License.
 
 use crate::lang_core::env::{
-    backend::Env,
+    backend::{ContainerKind, Env, ReentrantEnv},
     calldata::CallData,
     engine::{EnvInstance, OnInstance},
     error::Result,
@@ -19,7 +19,7 @@ use crate::lang_core::env::{
 };
 use liquid_prelude::{string::String, vec::Vec};
 use liquid_primitives::{
-    types::{timestamp, Address},
+    types::{timestamp, Address, Hash},
     Topics,
 };
 
@@ -47,6 +47,12 @@ pub fn remove_storage(key: &[u8]) {
     })
 }
 
+pub fn bind_container(key: &[u8], kind: ContainerKind) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::bind_container(instance, key, kind);
+    })
+}
+
 pub fn get_call_data(mode: CallMode) -> Result<CallData> {
     <EnvInstance as OnInstance>::on_instance(|instance| {
         Env::get_call_data(instance, mode)
@@ -79,6 +85,49 @@ pub fn get_block_number() -> u64 {
     <EnvInstance as OnInstance>::on_instance(|instance| Env::get_block_number(instance))
 }
 
+/// Returns the hash of current transaction.
+///
+/// On chain, it's only available with the `unstable-host-api` feature.
+#[cfg(any(feature = "std", feature = "unstable-host-api"))]
+pub fn get_tx_hash() -> Hash {
+    <EnvInstance as OnInstance>::on_instance(|instance| Env::get_tx_hash(instance))
+}
+
+/// Returns the hash of the block numbered `block_number`, only the 256 most
+/// recent blocks before current block are available.
+///
+/// On chain, it's only available with the `unstable-host-api` feature.
+#[cfg(any(feature = "std", feature = "unstable-host-api"))]
+pub fn get_block_hash(block_number: u64) -> Option<Hash> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::get_block_hash(instance, block_number)
+    })
+}
+
+/// Returns the ID of the chain.
+///
+/// On chain, it's only available with the `unstable-host-api` feature.
+#[cfg(any(feature = "std", feature = "unstable-host-api"))]
+pub fn get_chain_id() -> String {
+    <EnvInstance as OnInstance>::on_instance(|instance| Env::get_chain_id(instance))
+}
+
+/// Returns the ID of the group.
+///
+/// On chain, it's only available with the `unstable-host-api` feature.
+#[cfg(any(feature = "std", feature = "unstable-host-api"))]
+pub fn get_group_id() -> String {
+    <EnvInstance as OnInstance>::on_instance(|instance| Env::get_group_id(instance))
+}
+
+/// Returns the remaining gas of current transaction.
+///
+/// On chain, it's only available with the `unstable-host-api` feature.
+#[cfg(any(feature = "std", feature = "unstable-host-api"))]
+pub fn get_gas_left() -> u64 {
+    <EnvInstance as OnInstance>::on_instance(|instance| Env::get_gas_left(instance))
+}
+
 pub fn register_asset(
     asset_name: &[u8],
     issuer: &Address,
@@ -143,11 +192,69 @@ where
     <EnvInstance as OnInstance>::on_instance(|instance| Env::emit(instance, event));
 }
 
+/// Calls the contract at `addr` with `data` and decodes the returned value.
+///
+/// If the callee reverts, `EnvError::CallReverted` carrying the revert message
+/// will be returned.
 pub fn call<R>(addr: &Address, data: &[u8]) -> Result<R>
 where
     R: scale::Decode,
 {
-    <EnvInstance as OnInstance>::on_instance(|instance| Env::call(instance, addr, data))
+    <EnvInstance as ReentrantEnv>::call(addr, data)
+}
+
+/// Same as `call`, but the callee is guaranteed by the host not to modify the
+/// state, any attempt of modification will make the call fail.
+///
+/// On chain, it's only available with the `unstable-host-api` feature.
+#[cfg(any(feature = "std", feature = "unstable-host-api"))]
+pub fn call_static<R>(addr: &Address, data: &[u8]) -> Result<R>
+where
+    R: scale::Decode,
+{
+    <EnvInstance as ReentrantEnv>::call_static(addr, data)
+}
+
+/// Deploys a new contract with `code`, whose constructor is executed with
+/// `params` encoded in the same way as the constructor parameters of a
+/// deployment transaction. Returns the address of the new contract.
+///
+/// FISCO BCOS doesn't provide any host function to deploy contracts from
+/// contracts yet, so it's only available off chain.
+#[cfg(feature = "std")]
+pub fn create(code: &[u8], params: &[u8]) -> Result<Address> {
+    <EnvInstance as ReentrantEnv>::create(code, params)
+}
+
+/// Same as `create`, but the address of the new contract is determined by the
+/// address of current contract, `salt` and `code` only, hence it can be known
+/// before the deployment.
+#[cfg(feature = "std")]
+pub fn create2(code: &[u8], params: &[u8], salt: &Hash) -> Result<Address> {
+    <EnvInstance as ReentrantEnv>::create2(code, params, salt)
+}
+
+/// Recovers the address of the signer from the secp256k1 `signature` of
+/// `hash`, which is 65 bytes long and consists of `r`, `s` and the recovery id
+/// `v`. Returns `None` if the signature is invalid.
+///
+/// Off chain, it's only available with the `off-chain-crypto` feature.
+pub fn ecrecover(hash: &Hash, signature: &[u8]) -> Option<Address> {
+    <EnvInstance as ReentrantEnv>::ecrecover(hash, signature)
+}
+
+/// Returns whether the secp256k1 `signature` of `hash` is signed by `signer`.
+pub fn secp256k1_verify(hash: &Hash, signature: &[u8], signer: &Address) -> bool {
+    ecrecover(hash, signature).as_ref() == Some(signer)
+}
+
+/// Verifies the SM2 `signature` of `hash`, which is 64 bytes long and consists
+/// of `r` and `s`, with the 64 bytes uncompressed `public_key`. Returns the
+/// address of the public key if the signature is valid.
+///
+/// Off chain, it's only available with the `off-chain-crypto` feature.
+pub fn sm2_verify(hash: &Hash, signature: &[u8], public_key: &[u8]) -> Option<Address> {
+    <EnvInstance as ReentrantEnv>::sm2_verify(hash, signature, public_key)
 }
 
 pub fn finish<V>(return_value: &V)
@@ -13,7 +13,7 @@
 use crate::lang_core::env::{calldata::CallData, error::Result};
 use liquid_prelude::{string::String, vec::Vec};
 use liquid_primitives::{
-    types::{timestamp, Address},
+    types::{timestamp, Address, Hash},
     Topics,
 };
 
@@ -23,6 +23,24 @@ pub enum CallMode {
     Call,
 }
 
+/// The kind of a storage container bound to a key.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ContainerKind {
+    /// A single value stored at the key.
+    Value,
+    /// The length is stored at the key, and the `n`-th element is stored at
+    /// the key followed by `$` and `n` in little endian.
+    Vec,
+    /// The length is stored at the key, and each value is stored at the key
+    /// followed by `$` and the encoded key of value.
+    Mapping,
+    /// The slot of the front element and the length are stored at the key,
+    /// and the `n`-th slot of the ring is stored at the key followed by `$`
+    /// and `n` in little endian.
+    Deque,
+}
+
 pub trait Env {
     fn set_storage<V>(&mut self, key: &[u8], value: &V)
     where
@@ -34,6 +52,14 @@ pub trait Env {
 
     fn remove_storage(&mut self, key: &[u8]);
 
+    /// Notifies the environment that a storage container is bound to `key`.
+    ///
+    /// Nothing needs to be done on chain, while the off-chain environment
+    /// records the layout to decode raw storage keys to readable paths.
+    fn bind_container(&mut self, key: &[u8], kind: ContainerKind) {
+        let _ = (key, kind);
+    }
+
     fn get_call_data(&mut self, mode: CallMode) -> Result<CallData>;
 
     fn get_caller(&mut self) -> Address;
@@ -44,6 +70,21 @@ pub trait Env {
 
     fn get_block_number(&mut self) -> u64;
 
+    #[cfg(any(feature = "std", feature = "unstable-host-api"))]
+    fn get_tx_hash(&mut self) -> Hash;
+
+    #[cfg(any(feature = "std", feature = "unstable-host-api"))]
+    fn get_block_hash(&mut self, block_number: u64) -> Option<Hash>;
+
+    #[cfg(any(feature = "std", feature = "unstable-host-api"))]
+    fn get_chain_id(&mut self) -> String;
+
+    #[cfg(any(feature = "std", feature = "unstable-host-api"))]
+    fn get_group_id(&mut self) -> String;
+
+    #[cfg(any(feature = "std", feature = "unstable-host-api"))]
+    fn get_gas_left(&mut self) -> u64;
+
     fn get_address(&mut self) -> Address;
     fn get_external_code_size(&self, account: &Address) -> u32;
     fn register_asset(
@@ -93,10 +134,6 @@ pub trait Env {
     where
         Event: Topics + scale::Encode;
 
-    fn call<R>(&mut self, addr: &Address, data: &[u8]) -> Result<R>
-    where
-        R: scale::Decode;
-
     fn finish<V>(&mut self, return_value: &V)
     where
         V: scale::Encode;
@@ -105,3 +142,29 @@ pub trait Env {
     where
         V: scale::Encode;
 }
+
+/// Environmental functions which may re-enter the environment instance.
+///
+/// The off-chain engine executes the callee of a cross-contract call in place,
+/// and the callee accesses the environment by itself, so these functions must
+/// not hold the instance during the whole invocation.
+pub trait ReentrantEnv {
+    fn call<R>(addr: &Address, data: &[u8]) -> Result<R>
+    where
+        R: scale::Decode;
+
+    #[cfg(any(feature = "std", feature = "unstable-host-api"))]
+    fn call_static<R>(addr: &Address, data: &[u8]) -> Result<R>
+    where
+        R: scale::Decode;
+
+    #[cfg(feature = "std")]
+    fn create(code: &[u8], params: &[u8]) -> Result<Address>;
+
+    #[cfg(feature = "std")]
+    fn create2(code: &[u8], params: &[u8], salt: &Hash) -> Result<Address>;
+
+    fn ecrecover(hash: &Hash, signature: &[u8]) -> Option<Address>;
+
+    fn sm2_verify(hash: &Hash, signature: &[u8], public_key: &[u8]) -> Option<Address>;
+}
@@ -31,3 +31,9 @@ cfg_if! {
         }
     }
 }
+
+// The static buffer of the on-chain environment doesn't depend on the host,
+// so it's also compiled natively to run its unit tests.
+#[cfg(all(test, feature = "std"))]
+#[path = "on_chain/buffer.rs"]
+mod on_chain_buffer;
@@ -0,0 +1,99 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Signature verification and signing with pure-Rust implementations of
+//! secp256k1 and SM2, which behave the same as the precompiled contracts.
+
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use liquid_primitives::types::{Address, Hash};
+use signature::hazmat::{PrehashSigner, PrehashVerifier};
+use sm2::elliptic_curve::sec1::ToEncodedPoint;
+
+/// The default distinguishing identifier of SM2 signers.
+const SM2_DIST_ID: &str = "1234567812345678";
+
+/// Derives the address of an account from the hash of its 64 bytes
+/// uncompressed public key, which is the last 20 bytes of the hash in hex.
+///
+/// The hash algorithm depends on the signature algorithm rather than the `gm`
+/// feature, i.e., keccak256 for secp256k1 and SM3 for SM2.
+fn public_key_hash_to_address(hash: [u8; 32]) -> Address {
+    let mut addr = String::from("0x");
+    addr.push_str(&crate::bytes_to_hex(&hash[12..]));
+    addr.into()
+}
+
+fn secp256k1_address(public_key: &[u8]) -> Address {
+    public_key_hash_to_address(liquid_primitives::hash::keccak256(public_key))
+}
+
+fn sm2_address(public_key: &[u8]) -> Address {
+    public_key_hash_to_address(liquid_primitives::hash::sm3(public_key))
+}
+
+pub fn ecrecover(hash: &Hash, signature: &[u8]) -> Option<Address> {
+    if signature.len() != 65 {
+        return None;
+    }
+
+    let v = signature[64];
+    let recovery_id = RecoveryId::from_byte(if v >= 27 { v - 27 } else { v })?;
+    let sig = Signature::from_slice(&signature[..64]).ok()?;
+    let prehash = scale::Encode::encode(hash);
+    let verifying_key =
+        VerifyingKey::recover_from_prehash(&prehash, &sig, recovery_id).ok()?;
+    verifying_key.verify_prehash(&prehash, &sig).ok()?;
+    let public_key = verifying_key.to_encoded_point(false);
+    Some(secp256k1_address(&public_key.as_bytes()[1..]))
+}
+
+pub fn sm2_verify(hash: &Hash, signature: &[u8], public_key: &[u8]) -> Option<Address> {
+    if signature.len() != 64 || public_key.len() != 64 {
+        return None;
+    }
+
+    let mut sec1_bytes = vec![0x04];
+    sec1_bytes.extend_from_slice(public_key);
+    let verifying_key =
+        sm2::dsa::VerifyingKey::from_sec1_bytes(SM2_DIST_ID, &sec1_bytes).ok()?;
+    let sig = sm2::dsa::Signature::from_slice(signature).ok()?;
+    verifying_key
+        .verify_prehash(&scale::Encode::encode(hash), &sig)
+        .ok()?;
+    Some(sm2_address(public_key))
+}
+
+pub fn sign_secp256k1(secret_key: &[u8; 32], hash: &Hash) -> (Vec<u8>, Address) {
+    let signing_key =
+        SigningKey::from_slice(secret_key).expect("invalid secp256k1 secret key");
+    let (sig, recovery_id) = signing_key
+        .sign_prehash_recoverable(&scale::Encode::encode(hash))
+        .expect("failed to sign with secp256k1 secret key");
+    let mut signature = sig.to_bytes().to_vec();
+    signature.push(recovery_id.to_byte());
+
+    let public_key = signing_key.verifying_key().to_encoded_point(false);
+    (signature, secp256k1_address(&public_key.as_bytes()[1..]))
+}
+
+pub fn sign_sm2(secret_key: &[u8; 32], hash: &Hash) -> (Vec<u8>, Vec<u8>) {
+    let secret_key =
+        sm2::SecretKey::from_slice(secret_key).expect("invalid SM2 secret key");
+    let signing_key = sm2::dsa::SigningKey::new(SM2_DIST_ID, &secret_key)
+        .expect("invalid SM2 secret key");
+    let sig: sm2::dsa::Signature = signing_key
+        .sign_prehash(&scale::Encode::encode(hash))
+        .expect("failed to sign with SM2 secret key");
+
+    let public_key = secret_key.public_key().to_encoded_point(false);
+    (sig.to_bytes().to_vec(), public_key.as_bytes()[1..].to_vec())
+}
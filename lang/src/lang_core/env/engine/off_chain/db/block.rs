@@ -10,11 +10,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use liquid_primitives::types::Hash;
+
+#[derive(Clone, Debug)]
 pub struct Block {
     /// The current block number.
     block_number: u64,
     /// The timestamp of the block.
     timestamp: u64,
+    /// The hash of the block, which is derived from the block number and the
+    /// timestamp unless it is specified explicitly.
+    hash: Hash,
 }
 
 impl Block {
@@ -23,9 +29,16 @@ impl Block {
 
         let date_time = Utc::now();
         let timestamp = date_time.timestamp() as u64;
+        Self::with_timestamp(block_number, timestamp)
+    }
+
+    pub fn with_timestamp(block_number: u64, timestamp: u64) -> Self {
+        let mut preimage = block_number.to_le_bytes().to_vec();
+        preimage.extend_from_slice(&timestamp.to_le_bytes());
         Self {
             block_number,
             timestamp,
+            hash: liquid_primitives::hash::hash(&preimage).into(),
         }
     }
 
@@ -38,4 +51,13 @@ impl Block {
     pub fn timestamp(&self) -> u64 {
         self.timestamp
     }
+
+    /// Returns the hash of the block.
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    pub(crate) fn set_hash(&mut self, hash: Hash) {
+        self.hash = hash;
+    }
 }
@@ -14,6 +14,7 @@ use crate::lang_core::env::error::{EnvError, Result};
 use liquid_prelude::{collections::BTreeMap, vec::Vec};
 use scale::{Decode, Encode};
 
+#[derive(Clone)]
 pub struct ContractStorage {
     entries: BTreeMap<Vec<u8>, Vec<u8>>,
 }
@@ -35,6 +36,10 @@ impl ContractStorage {
         }
     }
 
+    pub fn get_raw(&self, key: &[u8]) -> Option<&[u8]> {
+        self.entries.get(key).map(|encoded| encoded.as_slice())
+    }
+
     pub fn set_storage<V>(&mut self, key: &[u8], value: &V)
     where
         V: Encode,
@@ -42,6 +47,10 @@ impl ContractStorage {
         self.entries.insert(key.to_vec(), value.encode());
     }
 
+    pub fn entries(&self) -> &BTreeMap<Vec<u8>, Vec<u8>> {
+        &self.entries
+    }
+
     pub fn remove_storage(&mut self, key: &[u8]) {
         self.entries.remove(key);
     }
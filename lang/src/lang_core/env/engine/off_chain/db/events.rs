@@ -10,6 +10,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::lang_core::env::engine::off_chain::test_api::DecodeEvent;
 use liquid_primitives::{types::Hash, Topics};
 use scale::{Decode, Encode};
 
@@ -17,6 +18,8 @@ use scale::{Decode, Encode};
 pub struct Event {
     pub data: Vec<u8>,
     pub topics: Vec<Hash>,
+    /// The encoded values of all fields, including the indexed ones.
+    pub fields: Vec<u8>,
 }
 
 impl Event {
@@ -27,6 +30,7 @@ impl Event {
         Self {
             data: event.encode(),
             topics: event.topics(),
+            fields: event.encode_fields(),
         }
     }
 
@@ -36,4 +40,20 @@ impl Event {
     {
         <R as Decode>::decode(&mut self.data.as_slice()).unwrap()
     }
+
+    /// Returns the signature of the event, which is the first topic.
+    pub fn signature(&self) -> Option<&Hash> {
+        self.topics.first()
+    }
+
+    /// Decodes the event as `T` if the signature matches.
+    pub fn decode<T>(&self) -> Option<T>
+    where
+        T: DecodeEvent,
+    {
+        if self.signature() != Some(&T::signature()) {
+            return None;
+        }
+        T::decode_fields(&mut self.fields.as_slice()).ok()
+    }
 }
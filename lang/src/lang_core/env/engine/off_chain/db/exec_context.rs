@@ -10,7 +10,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use liquid_primitives::types::Address;
+use liquid_primitives::types::{Address, Hash};
 
 pub struct ExecContext {
     /// The caller of the contract execution.
@@ -18,11 +18,38 @@ pub struct ExecContext {
     /// Might be user or another contract.
     pub caller: Address,
     pub callee: Address,
+    /// The origin of the transaction, if it is specified explicitly.
+    ///
+    /// Otherwise the caller of the outermost execution context is the origin.
+    pub origin: Option<Address>,
+    /// The hash of the transaction, if it is specified explicitly.
+    ///
+    /// Otherwise the hash of the transaction is all zeros.
+    pub tx_hash: Option<Hash>,
+    /// The raw call data passed to the callee.
+    pub call_data: Vec<u8>,
+    /// The encoded value passed to `finish`, if any.
+    pub output: Option<Vec<u8>>,
+    /// Whether the callee is executed in a static call, in which the state
+    /// must not be modified.
+    pub is_static: bool,
 }
 
 impl ExecContext {
     pub fn new(caller: Address, callee: Address) -> Self {
-        Self { caller, callee }
+        Self::with_call_data(caller, callee, Vec::new())
+    }
+
+    pub fn with_call_data(caller: Address, callee: Address, call_data: Vec<u8>) -> Self {
+        Self {
+            caller,
+            callee,
+            origin: None,
+            tx_hash: None,
+            call_data,
+            output: None,
+            is_static: false,
+        }
     }
 
     pub fn caller(&self) -> Address {
@@ -0,0 +1,111 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// The gas cost of each kind of operation.
+///
+/// The default values approximate the gas schedule of FISCO BCOS, which charges
+/// storage by 32-bytes words.
+#[derive(Clone, Debug)]
+pub struct CostTable {
+    /// The base cost of reading a key from storage.
+    pub storage_read: u64,
+    /// The cost of each byte read from storage.
+    pub storage_read_byte: u64,
+    /// The base cost of writing a key to storage.
+    pub storage_write: u64,
+    /// The cost of each byte written to storage.
+    pub storage_write_byte: u64,
+    /// The cost of removing a key from storage.
+    pub storage_remove: u64,
+    /// The base cost of emitting an event.
+    pub event: u64,
+    /// The cost of each topic of an event.
+    pub event_topic: u64,
+    /// The cost of each byte of the data of an event.
+    pub event_byte: u64,
+    /// The base cost of calling a contract.
+    pub call: u64,
+    /// The cost of each byte of the call data.
+    pub call_data_byte: u64,
+}
+
+impl Default for CostTable {
+    fn default() -> Self {
+        Self {
+            storage_read: 200,
+            storage_read_byte: 6,
+            storage_write: 5000,
+            storage_write_byte: 625,
+            storage_remove: 5000,
+            event: 375,
+            event_topic: 375,
+            event_byte: 8,
+            call: 700,
+            call_data_byte: 68,
+        }
+    }
+}
+
+/// The resources consumed during an execution.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GasUsage {
+    /// The number of keys read from storage.
+    pub storage_reads: u64,
+    /// The number of bytes of values read from storage.
+    pub storage_read_bytes: u64,
+    /// The number of keys written to storage.
+    pub storage_writes: u64,
+    /// The number of bytes of values written to storage.
+    pub storage_write_bytes: u64,
+    /// The number of keys removed from storage.
+    pub storage_removes: u64,
+    /// The number of emitted events.
+    pub events: u64,
+    /// The number of topics of emitted events.
+    pub event_topics: u64,
+    /// The number of bytes of the data of emitted events.
+    pub event_bytes: u64,
+    /// The number of contract calls.
+    pub calls: u64,
+    /// The number of bytes of the call data of contract calls.
+    pub call_data_bytes: u64,
+}
+
+impl GasUsage {
+    /// Returns the gas corresponding to the usage according to `table`.
+    pub fn gas(&self, table: &CostTable) -> u64 {
+        self.storage_reads * table.storage_read
+            + self.storage_read_bytes * table.storage_read_byte
+            + self.storage_writes * table.storage_write
+            + self.storage_write_bytes * table.storage_write_byte
+            + self.storage_removes * table.storage_remove
+            + self.events * table.event
+            + self.event_topics * table.event_topic
+            + self.event_bytes * table.event_byte
+            + self.calls * table.call
+            + self.call_data_bytes * table.call_data_byte
+    }
+
+    /// Accumulates `other` into the usage.
+    pub fn accumulate(&mut self, other: &Self) {
+        self.storage_reads += other.storage_reads;
+        self.storage_read_bytes += other.storage_read_bytes;
+        self.storage_writes += other.storage_writes;
+        self.storage_write_bytes += other.storage_write_bytes;
+        self.storage_removes += other.storage_removes;
+        self.events += other.events;
+        self.event_topics += other.event_topics;
+        self.event_bytes += other.event_bytes;
+        self.calls += other.calls;
+        self.call_data_bytes += other.call_data_bytes;
+    }
+}
@@ -0,0 +1,92 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::env::backend::ContainerKind;
+use std::collections::BTreeMap;
+
+const SEP: u8 = b'$';
+
+/// Records the keys bound to storage containers, which are used to decode raw
+/// storage keys back to readable field paths.
+#[derive(Default)]
+pub struct Layouts {
+    containers: BTreeMap<Vec<u8>, ContainerKind>,
+}
+
+impl Layouts {
+    pub fn register(&mut self, key: &[u8], kind: ContainerKind) {
+        self.containers.insert(key.to_vec(), kind);
+    }
+
+    /// Returns the readable path of a raw storage key, e.g. `total_supply`,
+    /// `balances/0x0c616c696365`, `values[3]`, `values.len`, `queue.bounds` or
+    /// `queue.slots[4294967295]`.
+    pub fn path_of(&self, key: &[u8]) -> String {
+        self.path_within(key, key.len() + 1)
+    }
+
+    /// Returns the readable path of a raw storage key, only the containers
+    /// bound to keys shorter than `max_prefix_len` are considered.
+    fn path_within(&self, key: &[u8], max_prefix_len: usize) -> String {
+        // The longest matched prefix is preferred, since keys of nested
+        // containers are derived from the keys of outer ones.
+        let matched = self
+            .containers
+            .iter()
+            .filter(|(prefix, _)| {
+                prefix.len() < max_prefix_len
+                    && key.starts_with(prefix)
+                    && (key.len() == prefix.len() || key[prefix.len()] == SEP)
+            })
+            .max_by_key(|(prefix, _)| prefix.len());
+
+        let (prefix, kind) = match matched {
+            Some(matched) => matched,
+            None => return readable(key),
+        };
+        // Containers nested in a `storage::Mapping` are bound with the key of
+        // a slot of the outer one, e.g., `allowances/0x01/0x02`.
+        let field = self.path_within(prefix, prefix.len());
+        if key.len() == prefix.len() {
+            return match kind {
+                ContainerKind::Value => field,
+                ContainerKind::Vec | ContainerKind::Mapping => format!("{}.len", field),
+                ContainerKind::Deque => format!("{}.bounds", field),
+            };
+        }
+
+        let index = &key[prefix.len() + 1..];
+        match kind {
+            ContainerKind::Vec if index.len() == 4 => {
+                let index = u32::from_le_bytes([index[0], index[1], index[2], index[3]]);
+                format!("{}[{}]", field, index)
+            }
+            // The slots of a deque are not the positions of elements, which
+            // depend on the slot of the front element.
+            ContainerKind::Deque if index.len() == 4 => {
+                let slot = u32::from_le_bytes([index[0], index[1], index[2], index[3]]);
+                format!("{}.slots[{}]", field, slot)
+            }
+            ContainerKind::Mapping => {
+                format!("{}/0x{}", field, crate::bytes_to_hex(index))
+            }
+            _ => readable(key),
+        }
+    }
+}
+
+fn readable(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(s) if s.chars().all(|c| !c.is_control()) => s.to_string(),
+        _ => format!("0x{}", crate::bytes_to_hex(bytes)),
+    }
+}
@@ -14,8 +14,12 @@ mod block;
 mod contract_storage;
 mod events;
 mod exec_context;
+mod gas;
+mod layout;
 
 pub use block::Block;
 pub use contract_storage::ContractStorage;
 pub use events::Event;
 pub use exec_context::ExecContext;
+pub use gas::{CostTable, GasUsage};
+pub use layout::Layouts;
@@ -10,46 +10,148 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "off-chain-crypto")]
+mod crypto;
 mod db;
 pub mod test_api;
 
-use self::db::{Block, ContractStorage, Event, ExecContext};
-use crate::lang_core::env::{
-    backend::Env, calldata::CallData, engine::OnInstance, error::Result, CallMode,
+use self::{
+    db::{Block, ContractStorage, CostTable, Event, ExecContext, GasUsage, Layouts},
+    test_api::Reverted,
+};
+use crate::{
+    lang_core::env::{
+        backend::{ContainerKind, Env, ReentrantEnv},
+        calldata::CallData,
+        engine::OnInstance,
+        error::{EnvError, Result},
+        CallMode,
+    },
+    DispatchResult, DispatchRetInfo,
 };
 use core::cell::RefCell;
-use liquid_primitives::{types::Address, Topics};
-use std::{collections::HashMap, str};
-
-struct AssetInfo {
-    issuer: Address,
-    fungible: bool,
-    total_supply: u64,
-    _description: String,
-    supplied: u64,
+use liquid_primitives::{
+    types::{Address, Hash},
+    Selector, Topics,
+};
+use std::{
+    collections::HashMap,
+    panic::{self, AssertUnwindSafe},
+    str,
+};
+
+/// The information of a registered asset.
+#[derive(Clone, Debug)]
+pub struct AssetInfo {
+    /// The account which is allowed to issue the asset.
+    pub issuer: Address,
+    /// Whether the asset is fungible.
+    pub fungible: bool,
+    /// The maximum amount of the asset which can be issued.
+    pub total_supply: u64,
+    /// The description of the asset.
+    pub description: String,
+    /// The amount of the asset which has been issued.
+    pub supplied: u64,
+}
+
+/// A contract registered in test environment.
+#[derive(Clone, Copy)]
+struct RegisteredContract {
+    deploy: fn(),
+    dispatch: fn() -> DispatchResult,
+    method_name: fn(Selector) -> Option<&'static str>,
+}
+
+impl RegisteredContract {
+    fn of<C>() -> Self
+    where
+        C: test_api::ContractEntry,
+    {
+        Self {
+            deploy: C::deploy,
+            dispatch: C::dispatch,
+            method_name: C::method_name,
+        }
+    }
 }
 
 pub struct EnvInstance {
-    contract_storage: ContractStorage,
+    contract_storages: HashMap<Address, ContractStorage>,
+    contracts: HashMap<Address, RegisteredContract>,
+    /// Contracts which can be deployed by `create` and `create2`, keyed by the
+    /// hash of their code.
+    codes: HashMap<Hash, RegisteredContract>,
+    /// The number of contracts created by each contract through `create`.
+    create_nonces: HashMap<Address, u64>,
     blocks: Vec<Block>,
     exec_contexts: Vec<ExecContext>,
     events: Vec<Event>,
     assets_info: HashMap<String, AssetInfo>,
     fungible_asset: HashMap<String, HashMap<Address, u64>>,
     not_fungible_asset: HashMap<String, HashMap<Address, HashMap<u64, String>>>,
+    /// The message passed to the latest `revert`, which hasn't been handled by
+    /// any call frame yet.
+    reverted: Option<String>,
+    cost_table: CostTable,
+    /// The resources consumed since the test environment was initialized.
+    gas_usage: GasUsage,
+    /// The resources consumed by each running metered execution, from the
+    /// outermost to the innermost.
+    gas_frames: Vec<GasUsage>,
+    /// The number of invocations and the consumed resources of each external
+    /// method, keyed by the address of contract and the name of method.
+    method_gas: HashMap<(Address, String), (u64, GasUsage)>,
+    layouts: Layouts,
+    chain_id: String,
+    group_id: String,
+    /// The gas limit of current transaction, and the gas which had been
+    /// consumed when the gas limit was set.
+    gas_limit: (u64, u64),
+}
+
+/// The default gas limit of transactions in FISCO BCOS.
+const DEFAULT_GAS_LIMIT: u64 = 300_000_000;
+
+/// The number of most recent blocks whose hashes are available to contracts.
+const BLOCK_HASH_WINDOW: u64 = 256;
+
+/// The state recorded at the start of a call frame, which will be restored
+/// when the call frame reverts.
+struct Snapshot {
+    contract_storages: HashMap<Address, ContractStorage>,
+    contracts: HashMap<Address, RegisteredContract>,
+    create_nonces: HashMap<Address, u64>,
+    exec_contexts_len: usize,
+    events_len: usize,
+    assets_info: HashMap<String, AssetInfo>,
+    fungible_asset: HashMap<String, HashMap<Address, u64>>,
+    not_fungible_asset: HashMap<String, HashMap<Address, HashMap<u64, String>>>,
 }
 
 impl Default for EnvInstance {
     fn default() -> Self {
         let blocks = vec![Block::new(0)];
         Self {
-            contract_storage: ContractStorage::new(),
+            contract_storages: HashMap::new(),
+            contracts: HashMap::new(),
+            codes: HashMap::new(),
+            create_nonces: HashMap::new(),
             blocks,
             exec_contexts: Vec::new(),
             events: Vec::new(),
             assets_info: HashMap::new(),
             fungible_asset: HashMap::new(),
             not_fungible_asset: HashMap::new(),
+            reverted: None,
+            cost_table: CostTable::default(),
+            gas_usage: GasUsage::default(),
+            gas_frames: Vec::new(),
+            method_gas: HashMap::new(),
+            layouts: Layouts::default(),
+            chain_id: String::from("chain0"),
+            group_id: String::from("group0"),
+            gas_limit: (DEFAULT_GAS_LIMIT, 0),
         }
     }
 }
@@ -67,15 +169,308 @@ impl EnvInstance {
             .expect("there must be at least one execution context in test environment")
     }
 
+    /// Returns the address of the contract being executed, or an empty address
+    /// if there is no execution context.
+    pub fn current_address(&self) -> Address {
+        self.exec_contexts
+            .last()
+            .map(|exec_context| exec_context.self_address())
+            .unwrap_or_default()
+    }
+
+    fn current_storage(&mut self) -> &mut ContractStorage {
+        let address = self.current_address();
+        self.contract_storages
+            .entry(address)
+            .or_insert_with(ContractStorage::new)
+    }
+
+    /// Reverts current call frame if it's executed in a static call, should be
+    /// called before any modification of the state.
+    fn ensure_mutable(&mut self) {
+        let is_static = self
+            .exec_contexts
+            .last()
+            .map_or(false, |exec_context| exec_context.is_static);
+        if is_static {
+            self.revert(&String::from("cannot modify the state in a static call"));
+        }
+    }
+
     pub fn current_block(&self) -> &Block {
         self.blocks
             .last()
             .expect("there must be at least one block in test environment")
     }
 
+    pub fn push_block(&mut self, block_number: u64, timestamp: u64) {
+        self.blocks
+            .push(Block::with_timestamp(block_number, timestamp));
+    }
+
     pub fn get_events(&self) -> std::slice::Iter<Event> {
         self.events.iter()
     }
+
+    /// Records the consumed resources into the total usage and the usage of all
+    /// running metered executions.
+    fn meter<F>(&mut self, f: F)
+    where
+        F: Fn(&mut GasUsage),
+    {
+        f(&mut self.gas_usage);
+        self.gas_frames.iter_mut().for_each(f);
+    }
+
+    /// Executes `f` and returns the resources consumed by it, including the
+    /// resources consumed by nested calls.
+    pub(crate) fn metered<F, R>(f: F) -> (R, GasUsage)
+    where
+        F: FnOnce() -> R,
+    {
+        // Pops the metered execution even if `f` panics.
+        struct GasFrameGuard(usize);
+
+        impl Drop for GasFrameGuard {
+            fn drop(&mut self) {
+                <EnvInstance as OnInstance>::on_instance(|instance| {
+                    instance.gas_frames.truncate(self.0);
+                })
+            }
+        }
+
+        let depth = <Self as OnInstance>::on_instance(|instance| {
+            instance.gas_frames.push(GasUsage::default());
+            instance.gas_frames.len() - 1
+        });
+        let guard = GasFrameGuard(depth);
+        let ret = f();
+        let usage = <Self as OnInstance>::on_instance(|instance| {
+            instance.gas_frames[depth].clone()
+        });
+        drop(guard);
+        (ret, usage)
+    }
+
+    /// Issues `amount` of fungible asset to `to` without checking the issuer.
+    fn mint_fungible_asset(
+        &mut self,
+        to: &Address,
+        asset_name: &str,
+        amount: u64,
+    ) -> bool {
+        let asset_info = match self.assets_info.get_mut(asset_name) {
+            Some(asset_info) if asset_info.fungible => asset_info,
+            _ => return false,
+        };
+        if asset_info.total_supply - asset_info.supplied < amount {
+            return false;
+        }
+        asset_info.supplied += amount;
+        let account_balance = self
+            .fungible_asset
+            .entry(asset_name.to_string())
+            .or_insert_with(HashMap::new)
+            .entry(to.clone())
+            .or_insert(0);
+        *account_balance += amount;
+        true
+    }
+
+    /// Issues a not fungible asset to `to` without checking the issuer, returns
+    /// the ID of the asset or 0 if failed.
+    fn mint_not_fungible_asset(
+        &mut self,
+        to: &Address,
+        asset_name: &str,
+        uri: &str,
+    ) -> u64 {
+        let asset_info = match self.assets_info.get_mut(asset_name) {
+            Some(asset_info) if !asset_info.fungible => asset_info,
+            _ => return 0,
+        };
+        if asset_info.total_supply == asset_info.supplied {
+            return 0;
+        }
+        asset_info.supplied += 1;
+        let tokens = self
+            .not_fungible_asset
+            .entry(asset_name.to_string())
+            .or_insert_with(HashMap::new)
+            .entry(to.clone())
+            .or_insert_with(HashMap::new);
+        tokens.insert(asset_info.supplied, uri.to_string());
+        asset_info.supplied
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            contract_storages: self.contract_storages.clone(),
+            contracts: self.contracts.clone(),
+            create_nonces: self.create_nonces.clone(),
+            exec_contexts_len: self.exec_contexts.len(),
+            events_len: self.events.len(),
+            assets_info: self.assets_info.clone(),
+            fungible_asset: self.fungible_asset.clone(),
+            not_fungible_asset: self.not_fungible_asset.clone(),
+        }
+    }
+
+    fn rollback(&mut self, snapshot: Snapshot) {
+        self.contract_storages = snapshot.contract_storages;
+        self.contracts = snapshot.contracts;
+        self.create_nonces = snapshot.create_nonces;
+        self.exec_contexts.truncate(snapshot.exec_contexts_len);
+        self.events.truncate(snapshot.events_len);
+        self.assets_info = snapshot.assets_info;
+        self.fungible_asset = snapshot.fungible_asset;
+        self.not_fungible_asset = snapshot.not_fungible_asset;
+    }
+
+    /// Dispatches `data` to the contract at `callee` in a new call frame, and
+    /// returns the output passed to `finish`.
+    ///
+    /// The call frame is static if `is_static` is true or the current call
+    /// frame is static.
+    fn invoke(
+        contract: RegisteredContract,
+        caller: Address,
+        callee: Address,
+        data: Vec<u8>,
+        is_static: bool,
+    ) -> core::result::Result<Vec<u8>, Reverted> {
+        let method = data
+            .get(..4)
+            .and_then(|selector| {
+                let selector = [selector[0], selector[1], selector[2], selector[3]];
+                (contract.method_name)(Selector::from_le_bytes(selector))
+            })
+            .map(String::from)
+            .unwrap_or_else(|| crate::bytes_to_hex(&data[..data.len().min(4)]));
+        let call_data_len = data.len() as u64;
+        <Self as OnInstance>::on_instance(|instance| {
+            let mut exec_context =
+                ExecContext::with_call_data(caller, callee.clone(), data);
+            exec_context.is_static = is_static
+                || instance
+                    .exec_contexts
+                    .last()
+                    .map_or(false, |exec_context| exec_context.is_static);
+            instance.exec_contexts.push(exec_context)
+        });
+
+        // The instance can't be borrowed here, because the callee will access
+        // the environment during dispatching.
+        let (result, usage) = Self::metered(|| {
+            <Self as OnInstance>::on_instance(|instance| {
+                instance.meter(|usage| {
+                    usage.calls += 1;
+                    usage.call_data_bytes += call_data_len;
+                })
+            });
+            Self::execute_frame(|| {
+                if let Err(err) = (contract.dispatch)() {
+                    let ret_info = DispatchRetInfo::from(err);
+                    crate::lang_core::env::revert(&ret_info.get_info_string());
+                }
+            })
+        });
+        let output = <Self as OnInstance>::on_instance(|instance| {
+            let (invocations, method_usage) =
+                instance.method_gas.entry((callee, method)).or_default();
+            *invocations += 1;
+            method_usage.accumulate(&usage);
+
+            instance
+                .exec_contexts
+                .pop()
+                .expect("the execution context of callee must exist")
+                .output
+        });
+        result.map(|_| output.unwrap_or_default())
+    }
+
+    /// Deploys the contract registered with `code` at the address returned by
+    /// `derive_address`, then executes its constructor with `params` in a new
+    /// call frame on behalf of current contract.
+    fn create_with<F>(code: &[u8], params: &[u8], derive_address: F) -> Result<Address>
+    where
+        F: FnOnce(&mut Self, &Address) -> Address,
+    {
+        let (contract, creator, addr) = <Self as OnInstance>::on_instance(|instance| {
+            if instance
+                .exec_contexts
+                .last()
+                .map_or(false, |exec_context| exec_context.is_static)
+            {
+                return Err(EnvError::FailToCreateContract);
+            }
+            let code_hash = Hash::from(liquid_primitives::hash::hash(code));
+            let contract = *instance
+                .codes
+                .get(&code_hash)
+                .ok_or(EnvError::FailToCreateContract)?;
+            let creator = instance.current_address();
+            let addr = derive_address(instance, &creator);
+            if instance.contracts.contains_key(&addr) {
+                return Err(EnvError::FailToCreateContract);
+            }
+            Ok((contract, creator, addr))
+        })?;
+
+        Self::execute_frame(|| {
+            <Self as OnInstance>::on_instance(|instance| {
+                instance.contracts.insert(addr.clone(), contract);
+                instance.exec_contexts.push(ExecContext::with_call_data(
+                    creator,
+                    addr.clone(),
+                    params.to_vec(),
+                ));
+            });
+            (contract.deploy)();
+            <Self as OnInstance>::on_instance(|instance| instance.exec_contexts.pop());
+        })
+        .map_err(|_| EnvError::FailToCreateContract)?;
+        Ok(addr)
+    }
+
+    /// Executes `f` in a new call frame.
+    ///
+    /// If `f` reverts, all changes made to storage, events and assets during
+    /// the execution will be discarded and the revert message will be returned.
+    /// Other panics will be propagated after discarding the changes.
+    pub(crate) fn execute_frame<F, R>(f: F) -> core::result::Result<R, Reverted>
+    where
+        F: FnOnce() -> R,
+    {
+        // A message left by a revert which wasn't caught by any call frame
+        // must not be taken as the cause of a panic in this frame.
+        let snapshot = <Self as OnInstance>::on_instance(|instance| {
+            instance.reverted = None;
+            instance.snapshot()
+        });
+        match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(ret) => {
+                <Self as OnInstance>::on_instance(|instance| instance.reverted = None);
+                Ok(ret)
+            }
+            Err(payload) => {
+                let reverted = <Self as OnInstance>::on_instance(|instance| {
+                    instance.rollback(snapshot);
+                    instance.reverted.take()
+                });
+                // The panic is only caused by reverting if it carries the
+                // message of the latest revert, otherwise the revert was caught
+                // within this frame before the panic.
+                match reverted {
+                    Some(msg) if payload.downcast_ref::<String>() == Some(&msg) => {
+                        Err(Reverted(msg))
+                    }
+                    _ => panic::resume_unwind(payload),
+                }
+            }
+        }
+    }
 }
 
 impl Env for EnvInstance {
@@ -83,22 +478,56 @@ impl Env for EnvInstance {
     where
         V: scale::Encode,
     {
-        self.contract_storage.set_storage(key, value);
+        self.ensure_mutable();
+        let storage = self.current_storage();
+        storage.set_storage(key, value);
+        let len = storage.get_raw(key).map_or(0, |encoded| encoded.len()) as u64;
+        self.meter(|usage| {
+            usage.storage_writes += 1;
+            usage.storage_write_bytes += len;
+        });
     }
 
     fn get_storage<R>(&mut self, key: &[u8]) -> Result<R>
     where
         R: scale::Decode,
     {
-        self.contract_storage.get_storage::<R>(key)
+        let storage = self.current_storage();
+        let len = storage.get_raw(key).map_or(0, |encoded| encoded.len()) as u64;
+        let ret = storage.get_storage::<R>(key);
+        self.meter(|usage| {
+            usage.storage_reads += 1;
+            usage.storage_read_bytes += len;
+        });
+        ret
     }
 
     fn remove_storage(&mut self, key: &[u8]) {
-        self.contract_storage.remove_storage(key);
+        self.ensure_mutable();
+        self.current_storage().remove_storage(key);
+        self.meter(|usage| usage.storage_removes += 1);
     }
 
-    fn get_call_data(&mut self, _: CallMode) -> Result<CallData> {
-        unimplemented!();
+    fn bind_container(&mut self, key: &[u8], kind: ContainerKind) {
+        self.layouts.register(key, kind);
+    }
+
+    fn get_call_data(&mut self, mode: CallMode) -> Result<CallData> {
+        let call_data = &self.current_exec_context().call_data;
+        if mode == CallMode::Call {
+            use scale::Decode;
+
+            // The call data of external methods must have a selector.
+            if call_data.len() < 4 {
+                return Err(EnvError::UnableToReadCallData);
+            }
+            CallData::decode(&mut call_data.as_slice()).map_err(Into::into)
+        } else {
+            Ok(CallData {
+                selector: 0,
+                data: call_data.clone(),
+            })
+        }
     }
 
     fn get_caller(&mut self) -> Address {
@@ -106,15 +535,25 @@ impl Env for EnvInstance {
     }
 
     fn get_tx_origin(&mut self) -> Address {
-        self.first_exec_context().caller()
+        self.exec_contexts
+            .iter()
+            .rev()
+            .find_map(|exec_context| exec_context.origin.clone())
+            .unwrap_or_else(|| self.first_exec_context().caller())
     }
 
     fn get_address(&mut self) -> Address {
         self.current_exec_context().self_address()
     }
 
-    fn get_external_code_size(&self, _account: &Address) -> u32 {
-        unimplemented!();
+    fn get_external_code_size(&self, account: &Address) -> u32 {
+        // There is no real code for contracts in test environment, so a
+        // placeholder size is returned for registered contracts.
+        if self.contracts.contains_key(account) {
+            1
+        } else {
+            0
+        }
     }
 
     fn now(&mut self) -> u64 {
@@ -125,25 +564,66 @@ impl Env for EnvInstance {
         self.current_block().block_number()
     }
 
-    fn emit<E>(&mut self, event: E)
-    where
-        E: Topics + scale::Encode,
-    {
-        self.events.push(Event::new(event));
+    fn get_tx_hash(&mut self) -> Hash {
+        self.exec_contexts
+            .iter()
+            .rev()
+            .find_map(|exec_context| exec_context.tx_hash)
+            .unwrap_or_default()
+    }
+
+    fn get_block_hash(&mut self, block_number: u64) -> Option<Hash> {
+        let current = self.current_block().block_number();
+        if block_number >= current || current - block_number > BLOCK_HASH_WINDOW {
+            return None;
+        }
+        self.blocks
+            .iter()
+            .rev()
+            .find(|block| block.block_number() == block_number)
+            .map(Block::hash)
+    }
+
+    fn get_chain_id(&mut self) -> String {
+        self.chain_id.clone()
+    }
+
+    fn get_group_id(&mut self) -> String {
+        self.group_id.clone()
+    }
+
+    fn get_gas_left(&mut self) -> u64 {
+        let (gas_limit, consumed) = self.gas_limit;
+        let gas_used = self
+            .gas_usage
+            .gas(&self.cost_table)
+            .saturating_sub(consumed);
+        gas_limit.saturating_sub(gas_used)
     }
 
-    fn call<R>(&mut self, _addr: &Address, _data: &[u8]) -> Result<R>
+    fn emit<E>(&mut self, event: E)
     where
-        R: scale::Decode,
+        E: Topics + scale::Encode,
     {
-        unimplemented!();
+        self.ensure_mutable();
+        let event = Event::new(event);
+        let (topics, len) = (event.topics.len() as u64, event.data.len() as u64);
+        self.meter(|usage| {
+            usage.events += 1;
+            usage.event_topics += topics;
+            usage.event_bytes += len;
+        });
+        self.events.push(event);
     }
 
-    fn finish<V>(&mut self, _: &V)
+    fn finish<V>(&mut self, return_value: &V)
     where
         V: scale::Encode,
     {
-        unimplemented!();
+        self.exec_contexts
+            .last_mut()
+            .expect("there must be at least one execution context in test environment")
+            .output = Some(return_value.encode());
     }
 
     fn revert<V>(&mut self, msg: &V)
@@ -151,10 +631,10 @@ impl Env for EnvInstance {
         V: scale::Encode,
     {
         // Ensure that the type of `V` can only be String.
-        panic!(
-            "{}",
-            <String as scale::Decode>::decode(&mut msg.encode().as_slice()).unwrap()
-        );
+        let msg =
+            <String as scale::Decode>::decode(&mut msg.encode().as_slice()).unwrap();
+        self.reverted = Some(msg.clone());
+        panic!("{}", msg);
     }
 
     fn register_asset(
@@ -165,6 +645,7 @@ impl Env for EnvInstance {
         total: u64,
         description: &[u8],
     ) -> bool {
+        self.ensure_mutable();
         let asset_name = str::from_utf8(asset_name).unwrap();
         if self.assets_info.contains_key(asset_name) {
             return false;
@@ -175,7 +656,7 @@ impl Env for EnvInstance {
                 issuer: issuer.clone(),
                 fungible,
                 total_supply: total,
-                _description: str::from_utf8(description).unwrap().to_string(),
+                description: str::from_utf8(description).unwrap().to_string(),
                 supplied: 0,
             },
         );
@@ -195,30 +676,18 @@ impl Env for EnvInstance {
         asset_name: &[u8],
         amount: u64,
     ) -> bool {
+        self.ensure_mutable();
         let asset_name = str::from_utf8(asset_name).unwrap();
         if !self.assets_info.contains_key(asset_name) {
             return false;
         }
         let caller = self.get_caller();
-        let mut asset_info = self.assets_info.get_mut(asset_name).unwrap();
-        if !asset_info.fungible {
-            return false;
-        }
-        if asset_info.issuer != caller {
-            return false;
-        }
-        if asset_info.total_supply - asset_info.supplied < amount {
-            return false;
+        match self.assets_info.get(asset_name) {
+            Some(asset_info) if asset_info.issuer == caller => {
+                self.mint_fungible_asset(to, asset_name, amount)
+            }
+            _ => false,
         }
-        asset_info.supplied += amount;
-        let account_balance = self
-            .fungible_asset
-            .entry(asset_name.to_string())
-            .or_insert_with(HashMap::new)
-            .entry(to.clone())
-            .or_insert(0);
-        *account_balance += amount;
-        true
     }
 
     fn issue_not_fungible_asset(
@@ -227,33 +696,18 @@ impl Env for EnvInstance {
         asset_name: &[u8],
         uri: &[u8],
     ) -> u64 {
+        self.ensure_mutable();
         let asset_name = str::from_utf8(asset_name).unwrap();
         if !self.assets_info.contains_key(asset_name) {
             return 0;
         }
         let caller = self.get_caller();
-        let mut asset_info = self.assets_info.get_mut(asset_name).unwrap();
-        if asset_info.fungible {
-            return 0;
-        }
-        if asset_info.issuer != caller {
-            return 0;
-        }
-        if asset_info.total_supply == asset_info.supplied {
-            return 0;
+        match self.assets_info.get(asset_name) {
+            Some(asset_info) if asset_info.issuer == caller => {
+                self.mint_not_fungible_asset(to, asset_name, str::from_utf8(uri).unwrap())
+            }
+            _ => 0,
         }
-        asset_info.supplied += 1;
-        let tokens = self
-            .not_fungible_asset
-            .entry(asset_name.to_string())
-            .or_insert_with(HashMap::new)
-            .entry(to.clone())
-            .or_insert_with(HashMap::new);
-        tokens.insert(
-            asset_info.supplied,
-            str::from_utf8(uri).unwrap().to_string(),
-        );
-        asset_info.supplied
     }
 
     fn transfer_asset(
@@ -263,6 +717,7 @@ impl Env for EnvInstance {
         amount_or_id: u64,
         from_self: bool,
     ) -> bool {
+        self.ensure_mutable();
         let asset_name = str::from_utf8(asset_name).unwrap();
         if !self.assets_info.contains_key(asset_name) {
             return false;
@@ -393,6 +848,99 @@ impl Env for EnvInstance {
     }
 }
 
+impl EnvInstance {
+    /// Calls the contract at `addr` with `data`, which is executed in a static
+    /// call frame if `is_static` is true.
+    fn call_with<R>(addr: &Address, data: &[u8], is_static: bool) -> Result<R>
+    where
+        R: scale::Decode,
+    {
+        let (contract, caller) = <Self as OnInstance>::on_instance(|instance| {
+            let contract = *instance
+                .contracts
+                .get(addr)
+                .ok_or(EnvError::FailToCallForeignContract)?;
+            Ok((contract, instance.current_address()))
+        })?;
+
+        let output =
+            Self::invoke(contract, caller, addr.clone(), data.to_vec(), is_static)
+                .map_err(|reverted| EnvError::CallReverted(reverted.0))?;
+        <R as scale::Decode>::decode(&mut output.as_slice()).map_err(Into::into)
+    }
+}
+
+impl ReentrantEnv for EnvInstance {
+    fn call<R>(addr: &Address, data: &[u8]) -> Result<R>
+    where
+        R: scale::Decode,
+    {
+        Self::call_with(addr, data, false)
+    }
+
+    fn call_static<R>(addr: &Address, data: &[u8]) -> Result<R>
+    where
+        R: scale::Decode,
+    {
+        Self::call_with(addr, data, true)
+    }
+
+    fn create(code: &[u8], params: &[u8]) -> Result<Address> {
+        Self::create_with(code, params, |instance, creator| {
+            let nonce = instance.create_nonces.entry(creator.clone()).or_insert(0);
+            *nonce += 1;
+
+            let mut preimage = creator.as_bytes().to_vec();
+            preimage.extend_from_slice(&nonce.to_le_bytes());
+            derive_address(&preimage)
+        })
+    }
+
+    fn create2(code: &[u8], params: &[u8], salt: &Hash) -> Result<Address> {
+        Self::create_with(code, params, |_, creator| {
+            let mut preimage = vec![0xff];
+            preimage.extend_from_slice(creator.as_bytes());
+            preimage.extend_from_slice(&scale::Encode::encode(salt));
+            preimage.extend_from_slice(&liquid_primitives::hash::hash(code));
+            derive_address(&preimage)
+        })
+    }
+
+    #[cfg(feature = "off-chain-crypto")]
+    fn ecrecover(hash: &Hash, signature: &[u8]) -> Option<Address> {
+        crypto::ecrecover(hash, signature)
+    }
+
+    #[cfg(not(feature = "off-chain-crypto"))]
+    fn ecrecover(_: &Hash, _: &[u8]) -> Option<Address> {
+        panic!("{}", NO_OFF_CHAIN_CRYPTO)
+    }
+
+    #[cfg(feature = "off-chain-crypto")]
+    fn sm2_verify(hash: &Hash, signature: &[u8], public_key: &[u8]) -> Option<Address> {
+        crypto::sm2_verify(hash, signature, public_key)
+    }
+
+    #[cfg(not(feature = "off-chain-crypto"))]
+    fn sm2_verify(_: &Hash, _: &[u8], _: &[u8]) -> Option<Address> {
+        panic!("{}", NO_OFF_CHAIN_CRYPTO)
+    }
+}
+
+#[cfg(not(feature = "off-chain-crypto"))]
+const NO_OFF_CHAIN_CRYPTO: &str = "the `off-chain-crypto` feature of `liquid_lang` is \
+                                   required to verify signatures in the off-chain \
+                                   environment";
+
+/// Derives the address of a created contract, which is the last 20 bytes of
+/// the hash of `preimage` in hex.
+fn derive_address(preimage: &[u8]) -> Address {
+    let hash = liquid_primitives::hash::hash(preimage);
+    let mut addr = String::from("0x");
+    addr.push_str(&crate::bytes_to_hex(&hash[12..]));
+    addr.into()
+}
+
 impl OnInstance for EnvInstance {
     fn on_instance<F, R>(f: F) -> R
     where
@@ -407,3 +955,524 @@ impl OnInstance for EnvInstance {
         INSTANCE.with(|instance| f(&mut instance.borrow_mut()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang_core::env::{self, test};
+
+    struct Echo;
+
+    impl test::ContractEntry for Echo {
+        fn deploy() {}
+
+        fn dispatch() -> DispatchResult {
+            let call_data = env::get_call_data(CallMode::Call)
+                .map_err(|_| crate::DispatchError::CouldNotReadInput)?;
+            let caller = env::get_caller();
+            env::finish(&(call_data.selector, call_data.data, caller));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn call_registered_contract() {
+        let accounts = test::default_accounts();
+        test::register_contract::<Echo>(accounts.bob.clone());
+        test::set_caller_callee(accounts.alice.clone(), accounts.charlie.clone());
+
+        let mut data = 42u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&[1, 2, 3]);
+        let (selector, input, caller) =
+            env::call::<(u32, Vec<u8>, Address)>(&accounts.bob, &data).unwrap();
+        assert_eq!(selector, 42);
+        assert_eq!(input, vec![1, 2, 3]);
+        assert_eq!(caller, accounts.charlie);
+        assert_eq!(env::get_address(), accounts.charlie);
+
+        assert!(env::call::<()>(&accounts.david, &data).is_err());
+        test::pop_execution_context();
+    }
+
+    struct Faulty;
+
+    impl test::ContractEntry for Faulty {
+        fn deploy() {}
+
+        fn dispatch() -> DispatchResult {
+            env::api::set_storage(b"faulty", &1u8);
+            env::revert(&String::from("out of service"));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn stale_revert_is_ignored() {
+        // Reverts outside of any call frame leave their messages behind.
+        let ret = panic::catch_unwind(|| env::revert(&String::from("stale")));
+        assert!(ret.is_err());
+
+        let ret = panic::catch_unwind(|| {
+            EnvInstance::execute_frame(|| panic!("index out of bounds"))
+        });
+        let payload = ret.expect_err("a non-revert panic must be propagated");
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"index out of bounds"));
+
+        // So do the reverts caught within a call frame.
+        let ret = panic::catch_unwind(|| {
+            EnvInstance::execute_frame(|| {
+                let ret = panic::catch_unwind(|| env::revert(&String::from("caught")));
+                assert!(ret.is_err());
+                panic!("assertion failed");
+            })
+        });
+        assert!(ret.is_err());
+
+        assert_eq!(
+            EnvInstance::execute_frame(|| env::revert(&String::from("rejected"))),
+            Err(Reverted(String::from("rejected")))
+        );
+    }
+
+    #[test]
+    fn rollback_on_revert() {
+        let accounts = test::default_accounts();
+        test::register_contract::<Faulty>(accounts.eve.clone());
+        assert!(env::call::<()>(&accounts.eve, &[0u8; 4]).is_err());
+
+        let ret = test::catch_revert(|| {
+            test::set_caller_callee(accounts.alice.clone(), accounts.frank.clone());
+            env::api::set_storage(b"key", &42u32);
+            assert_eq!(env::api::get_storage::<u32>(b"key").unwrap(), 42);
+            env::revert(&String::from("rejected"));
+        });
+        assert_eq!(ret, Err(test::Reverted(String::from("rejected"))));
+
+        test::set_caller_callee(accounts.alice, accounts.frank);
+        assert!(env::api::get_storage::<u32>(b"key").is_err());
+        test::set_caller_callee(accounts.bob, accounts.eve);
+        assert!(env::api::get_storage::<u8>(b"faulty").is_err());
+        test::pop_execution_context();
+        test::pop_execution_context();
+    }
+
+    #[test]
+    fn call_raw_returns_output_or_revert_message() {
+        use scale::Encode;
+
+        let accounts = test::default_accounts();
+        test::register_contract::<Echo>(accounts.bob.clone());
+        test::register_contract::<Faulty>(accounts.eve.clone());
+        assert_eq!(env::get_external_code_size(&accounts.bob), 1);
+        assert_eq!(env::get_external_code_size(&accounts.david), 0);
+
+        let output = test::call_raw(&accounts.bob, &[7, 0, 0, 0, 1]).unwrap();
+        assert_eq!(output, (7u32, vec![1u8], Address::default()).encode());
+        assert_eq!(
+            test::call_raw(&accounts.bob, &[7]),
+            Err(String::from("could not read input").encode())
+        );
+        assert_eq!(
+            test::call_raw(&accounts.eve, &[0u8; 4]),
+            Err(String::from("out of service").encode())
+        );
+    }
+
+    #[test]
+    fn static_call_rejects_modifications() {
+        let accounts = test::default_accounts();
+        test::register_contract::<Echo>(accounts.bob.clone());
+        test::register_contract::<Faulty>(accounts.eve.clone());
+
+        let (selector, ..) =
+            env::call_static::<(u32, Vec<u8>, Address)>(&accounts.bob, &[7, 0, 0, 0])
+                .unwrap();
+        assert_eq!(selector, 7);
+        assert!(matches!(
+            env::call::<()>(&accounts.eve, &[0u8; 4]),
+            Err(EnvError::CallReverted(msg)) if msg == "out of service"
+        ));
+        assert!(matches!(
+            env::call_static::<()>(&accounts.eve, &[0u8; 4]),
+            Err(EnvError::CallReverted(msg))
+                if msg == "cannot modify the state in a static call"
+        ));
+        assert!(matches!(
+            env::call_static::<()>(&accounts.david, &[0u8; 4]),
+            Err(EnvError::FailToCallForeignContract)
+        ));
+    }
+
+    struct Counter;
+
+    impl test::ContractEntry for Counter {
+        fn deploy() {
+            let call_data = env::get_call_data(CallMode::Deploy).unwrap();
+            let init =
+                <u32 as scale::Decode>::decode(&mut call_data.data.as_slice()).unwrap();
+            if init == 0 {
+                env::revert(&String::from("zero is not allowed"));
+            }
+            env::api::set_storage(b"count", &init);
+        }
+
+        fn dispatch() -> DispatchResult {
+            env::finish(&env::api::get_storage::<u32>(b"count").unwrap());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn deploy_reverting_contract() {
+        use scale::Encode;
+
+        let accounts = test::default_accounts();
+        let _guard = test::as_caller(accounts.alice.clone());
+        assert_eq!(
+            test::deploy_contract::<Counter, _>(accounts.bob.clone(), (0u32,)),
+            Err(Reverted(String::from("zero is not allowed")))
+        );
+        assert!(!test::is_contract_registered(&accounts.bob));
+        assert_eq!(env::get_caller(), accounts.alice);
+        assert!(test::storage_snapshot_of(&accounts.bob).dump().is_empty());
+
+        assert_eq!(
+            test::deploy_contract::<Counter, _>(accounts.bob.clone(), (1u32,)),
+            Ok(())
+        );
+        assert!(test::is_contract_registered(&accounts.bob));
+        assert_eq!(env::get_caller(), accounts.alice);
+        assert_eq!(env::call::<u32>(&accounts.bob, &[0u8; 4]).unwrap(), 1);
+        let storage = test::storage_snapshot_of(&accounts.bob).dump();
+        assert_eq!(storage.len(), 1);
+        assert_eq!(storage[0].value, 1u32.encode());
+    }
+
+    #[test]
+    fn create_registered_code() {
+        use scale::Encode;
+
+        let accounts = test::default_accounts();
+        test::register_code::<Counter>(b"counter");
+        test::set_caller_callee(accounts.alice.clone(), accounts.bob.clone());
+
+        let first = env::create(b"counter", &1u32.encode()).unwrap();
+        let second = env::create(b"counter", &2u32.encode()).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(env::call::<u32>(&first, &[0u8; 4]).unwrap(), 1);
+        assert_eq!(env::call::<u32>(&second, &[0u8; 4]).unwrap(), 2);
+        assert_eq!(env::get_external_code_size(&first), 1);
+        assert_eq!(env::get_address(), accounts.bob);
+
+        assert!(env::create(b"unknown", &1u32.encode()).is_err());
+        assert!(env::create(b"counter", &0u32.encode()).is_err());
+        assert_eq!(env::get_address(), accounts.bob);
+
+        let salt = Hash::from([7u8; 32]);
+        let third = env::create2(b"counter", &3u32.encode(), &salt).unwrap();
+        assert!(env::create2(b"counter", &4u32.encode(), &salt).is_err());
+        assert_eq!(env::call::<u32>(&third, &[0u8; 4]).unwrap(), 3);
+        test::pop_execution_context();
+    }
+
+    #[cfg(feature = "off-chain-crypto")]
+    #[test]
+    fn verify_signatures() {
+        let hash = Hash::from(liquid_primitives::hash::hash(b"order"));
+        let other_hash = Hash::from(liquid_primitives::hash::hash(b"another order"));
+
+        let (mut signature, signer) = test::sign_secp256k1(&[1u8; 32], &hash);
+        assert_eq!(env::ecrecover(&hash, &signature), Some(signer.clone()));
+        assert!(env::secp256k1_verify(&hash, &signature, &signer));
+        assert!(!env::secp256k1_verify(&other_hash, &signature, &signer));
+        assert_eq!(env::ecrecover(&hash, &signature[..64]), None);
+        signature[64] += 27;
+        assert_eq!(env::ecrecover(&hash, &signature), Some(signer));
+
+        let (signature, public_key) = test::sign_sm2(&[1u8; 32], &hash);
+        assert!(env::sm2_verify(&hash, &signature, &public_key).is_some());
+        assert_eq!(env::sm2_verify(&other_hash, &signature, &public_key), None);
+        let (_, other_public_key) = test::sign_sm2(&[2u8; 32], &hash);
+        assert_ne!(public_key, other_public_key);
+        assert_eq!(env::sm2_verify(&hash, &signature, &other_public_key), None);
+    }
+
+    #[cfg(feature = "off-chain-crypto")]
+    #[test]
+    fn signature_known_answers() {
+        use hex_literal::hex;
+
+        let hash = Hash::from(liquid_primitives::hash::keccak256(b"liquid"));
+
+        // The address of the secp256k1 secret key 1 is derived with keccak256
+        // regardless of the `gm` feature.
+        let mut secret_key = [0u8; 32];
+        secret_key[31] = 1;
+        let signer = Address::from("0x7e5f4552091a69125d5dfcb7b8c2659029395bdf");
+        let (_, address) = test::sign_secp256k1(&secret_key, &hash);
+        assert_eq!(address, signer);
+        let signature = hex!(
+            "
+            bb50e2d89a4ed70663d080659fe0ad4b9bc3e06c17a227433966cb59ceee020d
+            5593b6b61f9510185bd407e9c93e002ac32b79ed650905bc02fd58b3ac96b2f9
+            1c
+            "
+        );
+        assert_eq!(env::ecrecover(&hash, &signature), Some(signer));
+
+        // The key pair from the SM2 example of GM/T 0003, whose address is
+        // derived with SM3 regardless of the `gm` feature.
+        let public_key = hex!(
+            "
+            09f9df311e5421a150dd7d161e4bc5c672179fad1833fc076bb08ff356f35020
+            ccea490ce26775a52dc6ea718cc1aa600aed05fbf35e084a6632f6072da9ad13
+            "
+        );
+        let signature = hex!(
+            "
+            f381b7b0f96572c1a6a791f60030a24268dd67597850b213779a1bf7f5ff5afa
+            ba563ee810e60d22906d8d9ef5fcb256aa2d13136b9b3f359a1393b52d9aaf58
+            "
+        );
+        assert_eq!(
+            env::sm2_verify(&hash, &signature, &public_key),
+            Some(Address::from("0x2119e1ecc071c2f3d915245c8d9c5a2cb131a89d"))
+        );
+        let secret_key =
+            hex!("3945208f7b2144b13f36e38ac6d39f95889393692860b51a42fb81ef4df7c5b8");
+        let (_, derived_public_key) = test::sign_sm2(&secret_key, &hash);
+        assert_eq!(derived_public_key, public_key.to_vec());
+    }
+
+    #[test]
+    fn time_travel() {
+        let genesis = env::now();
+        test::advance_block();
+        assert_eq!(env::api::get_block_number(), 1);
+        assert_eq!(env::now(), genesis);
+
+        test::advance_time(60);
+        assert_eq!(env::api::get_block_number(), 2);
+        assert_eq!(env::now(), genesis + 60);
+
+        test::set_block_number(100);
+        test::set_block_timestamp(42);
+        assert_eq!(env::api::get_block_number(), 101);
+        assert_eq!(env::now(), 42);
+
+        let blocks = test::get_blocks();
+        assert_eq!(blocks.len(), 5);
+        assert_eq!(blocks[3].block_number(), 100);
+        assert_eq!(blocks[3].timestamp(), genesis + 60);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the new block number 2 must be greater than the current one 2"
+    )]
+    fn set_block_number_backwards() {
+        test::advance_block();
+        test::set_block_number(2);
+        assert_eq!(env::api::get_block_number(), 2);
+        test::set_block_number(2);
+    }
+
+    #[test]
+    fn tx_and_block_metadata() {
+        let accounts = test::default_accounts();
+        test::advance_block();
+        test::advance_block();
+        let blocks = test::get_blocks();
+        assert_eq!(env::api::get_block_hash(0), Some(blocks[0].hash()));
+        assert_ne!(env::api::get_block_hash(0), env::api::get_block_hash(1));
+        assert_eq!(env::api::get_block_hash(2), None);
+
+        test::set_block_hash(Hash::from([1u8; 32]));
+        test::advance_block();
+        assert_eq!(env::api::get_block_hash(2), Some(Hash::from([1u8; 32])));
+        test::set_block_number(300);
+        assert_eq!(env::api::get_block_hash(2), None);
+
+        let _guard = test::as_caller_callee(accounts.alice, accounts.bob);
+        assert_eq!(env::api::get_tx_hash(), Hash::default());
+        test::set_tx_hash(Hash::from([2u8; 32]));
+        test::with_caller(accounts.charlie, || {
+            assert_eq!(env::api::get_tx_hash(), Hash::from([2u8; 32]));
+        });
+
+        assert_eq!(env::api::get_chain_id(), "chain0");
+        assert_eq!(env::api::get_group_id(), "group0");
+        test::set_chain_id("chain1");
+        test::set_group_id("group1");
+        assert_eq!(env::api::get_chain_id(), "chain1");
+        assert_eq!(env::api::get_group_id(), "group1");
+
+        test::set_gas_limit(10_000);
+        assert_eq!(env::api::get_gas_left(), 10_000);
+        env::api::set_storage(b"key", &1u8);
+        let cost_table = test::get_cost_table();
+        assert_eq!(
+            env::api::get_gas_left(),
+            10_000 - cost_table.storage_write - cost_table.storage_write_byte
+        );
+    }
+
+    #[test]
+    fn exec_context_guard() {
+        let accounts = test::default_accounts();
+        {
+            let _guard =
+                test::as_caller_callee(accounts.alice.clone(), accounts.bob.clone());
+            assert_eq!(env::get_caller(), accounts.alice);
+            test::with_caller(accounts.charlie.clone(), || {
+                assert_eq!(env::get_caller(), accounts.charlie);
+                assert_eq!(env::get_address(), accounts.bob);
+                assert_eq!(env::api::get_tx_origin(), accounts.alice);
+            });
+
+            let _guard = test::as_context(
+                accounts.david.clone(),
+                accounts.eve.clone(),
+                accounts.frank.clone(),
+            );
+            assert_eq!(env::api::get_tx_origin(), accounts.david);
+            assert_eq!(env::get_caller(), accounts.eve);
+            assert_eq!(env::get_address(), accounts.frank);
+        }
+
+        let result = std::panic::catch_unwind(|| {
+            let _guard = test::as_caller(accounts.alice.clone());
+            panic!("interrupted");
+        });
+        assert!(result.is_err());
+        assert!(<EnvInstance as OnInstance>::on_instance(|instance| {
+            instance.exec_contexts.is_empty()
+        }));
+
+        test::set_caller(accounts.alice);
+        test::reset();
+        assert!(<EnvInstance as OnInstance>::on_instance(|instance| {
+            instance.exec_contexts.is_empty()
+        }));
+    }
+
+    #[test]
+    fn seed_assets() {
+        let accounts = test::default_accounts();
+        env::register_asset(b"coin", &accounts.alice, true, 100, b"a coin");
+        env::register_asset(b"art", &accounts.alice, false, 2, b"an art");
+
+        test::seed_fungible_asset(&accounts.bob, "coin", 30);
+        assert_eq!(env::get_asset_balance(&accounts.bob, b"coin"), 30);
+        let id = test::seed_not_fungible_asset(&accounts.charlie, "art", "ipfs://art");
+        assert_eq!(id, 1);
+
+        let assets = test::get_assets();
+        assert_eq!(assets.len(), 2);
+        assert_eq!(assets["coin"].supplied, 30);
+        assert_eq!(assets["coin"].description, "a coin");
+        assert_eq!(test::get_asset("art").unwrap().issuer, accounts.alice);
+        assert_eq!(test::get_fungible_asset_holders("coin")[&accounts.bob], 30);
+        assert_eq!(
+            test::get_not_fungible_asset_holders("art")[&accounts.charlie][&1],
+            "ipfs://art"
+        );
+    }
+
+    #[test]
+    fn gas_metering() {
+        let accounts = test::default_accounts();
+        test::register_contract::<Faulty>(accounts.eve.clone());
+
+        let (_, usage) = test::metered(|| {
+            env::api::set_storage(b"key", &42u32);
+            let _ = env::api::get_storage::<u32>(b"key");
+            assert!(test::call_raw(&accounts.eve, &[1, 2, 3, 4, 5]).is_err());
+        });
+        assert_eq!(usage.storage_writes, 2);
+        assert_eq!(usage.storage_write_bytes, 5);
+        assert_eq!(usage.storage_reads, 1);
+        assert_eq!(usage.storage_read_bytes, 4);
+        assert_eq!(usage.calls, 1);
+        assert_eq!(usage.call_data_bytes, 5);
+        assert_eq!(test::gas_usage(), usage);
+
+        let report = test::gas_report();
+        let method_gas = &report[&(accounts.eve, String::from("01020304"))];
+        assert_eq!(method_gas.invocations, 1);
+        assert_eq!(method_gas.usage.storage_writes, 1);
+        assert_eq!(
+            method_gas.gas,
+            method_gas.usage.gas(&test::get_cost_table())
+        );
+    }
+
+    #[test]
+    fn storage_diff_with_paths() {
+        use crate::lang_core::storage::{Bind, Flush, IterableMapping, Value};
+
+        let mut supply = Value::<u32>::bind_with(b"supply");
+        let mut balances = IterableMapping::<u8, u32>::bind_with(b"balances");
+        balances.initialize();
+        balances.insert(1, 100);
+        balances.flush();
+
+        let before = test::storage_snapshot();
+        supply.set(100);
+        supply.flush();
+        balances.insert(2, 200);
+        balances.flush();
+        let after = test::storage_snapshot();
+
+        let paths = test::storage_diff(&before, &after)
+            .iter()
+            .map(|change| change.path().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "balances$keys.len",
+                "balances$keys[1]",
+                "balances$mapping.len",
+                "balances$mapping/0x02",
+                "supply",
+            ]
+        );
+        assert_eq!(test::dump_storage().len(), 7);
+    }
+
+    #[test]
+    fn nested_key_paths() {
+        use crate::lang_core::storage::{Bind, Flush, Mapping};
+
+        let mut allowances = Mapping::<u8, Mapping<u8, u32>>::bind_with(b"allowances");
+        allowances[&1].insert(2, 100);
+        allowances.flush();
+
+        let paths = test::dump_storage()
+            .into_iter()
+            .map(|entry| entry.path)
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["allowances/0x01.len", "allowances/0x01/0x02"]);
+    }
+
+    #[test]
+    fn deque_key_paths() {
+        use crate::lang_core::storage::{Bind, Deque, Flush};
+
+        let mut queue = Deque::<u32>::bind_with(b"queue");
+        queue.initialize();
+        queue.push_back(1);
+        queue.push_front(0);
+        queue.flush();
+
+        let paths = test::dump_storage()
+            .into_iter()
+            .map(|entry| entry.path)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec!["queue.bounds", "queue.slots[0]", "queue.slots[4294967295]"]
+        );
+    }
+}
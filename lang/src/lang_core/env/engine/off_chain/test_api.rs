@@ -10,9 +10,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{EnvInstance, Event, ExecContext};
-use crate::lang_core::env::engine::OnInstance;
-use liquid_primitives::types::Address;
+pub use super::{
+    db::{CostTable, GasUsage},
+    AssetInfo,
+};
+pub use crate::lang_core::env::backend::ContainerKind;
+
+use super::{Block, EnvInstance, Event, ExecContext, RegisteredContract};
+use crate::{lang_core::env::engine::OnInstance, DispatchResult};
+use liquid_primitives::{
+    types::{Address, Hash},
+    Selector,
+};
+use std::collections::BTreeMap;
 
 /// Pushes a contract execution context.
 ///
@@ -58,6 +68,86 @@ pub fn pop_execution_context() {
     })
 }
 
+/// A guard which restores the previous execution context when dropped.
+///
+/// The guard is dropped during unwinding as well, so a panicking test won't
+/// leave its execution context behind.
+#[must_use = "the execution context is popped immediately if the guard is unused"]
+pub struct ExecContextGuard {
+    depth: usize,
+}
+
+impl Drop for ExecContextGuard {
+    fn drop(&mut self) {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            instance.exec_contexts.truncate(self.depth);
+        })
+    }
+}
+
+fn enter_exec_context(exec_context: ExecContext) -> ExecContextGuard {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let depth = instance.exec_contexts.len();
+        instance.exec_contexts.push(exec_context);
+        ExecContextGuard { depth }
+    })
+}
+
+/// Executes the following code on behalf of `caller` until the returned guard
+/// is dropped.
+///
+/// The callee address is inherited from current execution context.
+pub fn as_caller(caller: Address) -> ExecContextGuard {
+    let callee =
+        <EnvInstance as OnInstance>::on_instance(|instance| instance.current_address());
+    enter_exec_context(ExecContext::new(caller, callee))
+}
+
+/// Executes the following code on behalf of `caller` with `callee` as the
+/// address of contract until the returned guard is dropped.
+pub fn as_caller_callee(caller: Address, callee: Address) -> ExecContextGuard {
+    enter_exec_context(ExecContext::new(caller, callee))
+}
+
+/// Executes the following code as a transaction sent by `origin`, in which
+/// `callee` is called by `caller`, until the returned guard is dropped.
+pub fn as_context(origin: Address, caller: Address, callee: Address) -> ExecContextGuard {
+    let mut exec_context = ExecContext::new(caller, callee);
+    exec_context.origin = Some(origin);
+    enter_exec_context(exec_context)
+}
+
+/// Executes `f` on behalf of `caller`, the previous execution context will be
+/// restored after `f` returned or panicked.
+pub fn with_caller<F, R>(caller: Address, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let _guard = as_caller(caller);
+    f()
+}
+
+/// Executes `f` on behalf of `caller` with `callee` as the address of
+/// contract, the previous execution context will be restored after `f`
+/// returned or panicked.
+pub fn with_caller_callee<F, R>(caller: Address, callee: Address, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let _guard = as_caller_callee(caller, callee);
+    f()
+}
+
+/// Resets the test environment of current thread to the initial state.
+///
+/// All storage, registered contracts, blocks, execution contexts, events and
+/// assets are cleared.
+pub fn reset() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        *instance = EnvInstance::default();
+    })
+}
+
 /// The default accounts.
 pub struct DefaultAccounts {
     pub alice: Address,
@@ -82,9 +172,701 @@ pub fn default_accounts() -> DefaultAccounts {
     }
 }
 
+/// Events which can be decoded from the records in test environment.
+///
+/// This trait is implemented by `#[liquid(event)]` structs automatically.
+pub trait DecodeEvent: Sized {
+    /// Returns the signature of the event, i.e., the first topic.
+    fn signature() -> Hash;
+
+    /// Decodes all fields of the event in declaration order.
+    fn decode_fields(fields: &mut &[u8]) -> Result<Self, scale::Error>;
+}
+
+/// The recorded emitted events.
+#[derive(Clone, Debug)]
+pub struct Events(Vec<Event>);
+
+impl Events {
+    /// Decodes all events of type `T` in order, other events are ignored.
+    pub fn decode<T>(&self) -> Vec<T>
+    where
+        T: DecodeEvent,
+    {
+        self.0
+            .iter()
+            .filter_map(|event| event.decode::<T>())
+            .collect()
+    }
+
+    /// Returns the events whose signature is `signature`.
+    pub fn with_signature(&self, signature: &Hash) -> Self {
+        self.filter(|event| event.signature() == Some(signature))
+    }
+
+    /// Returns the events which have `topic` among their topics.
+    pub fn with_topic(&self, topic: &Hash) -> Self {
+        self.filter(|event| event.topics.contains(topic))
+    }
+
+    /// Returns the events satisfying the predicate.
+    pub fn filter<P>(&self, mut predicate: P) -> Self
+    where
+        P: FnMut(&Event) -> bool,
+    {
+        Self(
+            self.0
+                .iter()
+                .filter(|event| predicate(event))
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+impl core::ops::Deref for Events {
+    type Target = [Event];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl IntoIterator for Events {
+    type Item = Event;
+    type IntoIter = std::vec::IntoIter<Event>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
 /// Returns the recorded emitted events in order.
 pub fn get_events() -> Vec<Event> {
     <EnvInstance as OnInstance>::on_instance(|instance| {
         instance.get_events().cloned().collect::<Vec<_>>()
     })
 }
+
+/// Returns the recorded emitted events in order, which can be decoded to typed
+/// events or filtered by signature and topics.
+pub fn events() -> Events {
+    Events(get_events())
+}
+
+/// Clears the recorded emitted events.
+pub fn clear_events() {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.events.clear())
+}
+
+/// Produces a new block whose number is one more than the current block, with
+/// the same timestamp as the current block.
+pub fn advance_block() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let current = instance.current_block();
+        let (block_number, timestamp) = (current.block_number(), current.timestamp());
+        instance.push_block(block_number + 1, timestamp);
+    })
+}
+
+/// Produces a new block whose timestamp is `timestamp`.
+pub fn set_block_timestamp(timestamp: u64) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let block_number = instance.current_block().block_number();
+        instance.push_block(block_number + 1, timestamp);
+    })
+}
+
+/// Produces a new block whose timestamp is `secs` seconds later than the
+/// current block.
+pub fn advance_time(secs: u64) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let current = instance.current_block();
+        let (block_number, timestamp) = (current.block_number(), current.timestamp());
+        instance.push_block(block_number + 1, timestamp + secs);
+    })
+}
+
+/// Produces a new block whose number is `block_number`, with the same timestamp
+/// as the current block.
+///
+/// # Panics
+///
+/// If `block_number` is not greater than the number of the current block.
+pub fn set_block_number(block_number: u64) {
+    let (current, timestamp) = <EnvInstance as OnInstance>::on_instance(|instance| {
+        let current = instance.current_block();
+        (current.block_number(), current.timestamp())
+    });
+    assert!(
+        block_number > current,
+        "the new block number {} must be greater than the current one {}",
+        block_number,
+        current
+    );
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.push_block(block_number, timestamp);
+    })
+}
+
+/// Returns all produced blocks in order, the last one is the current block.
+pub fn get_blocks() -> Vec<Block> {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.blocks.clone())
+}
+
+/// Sets the hash of the current block.
+pub fn set_block_hash(hash: Hash) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .blocks
+            .last_mut()
+            .expect("there must be at least one block in test environment")
+            .set_hash(hash);
+    })
+}
+
+/// Sets the hash of the transaction which current execution context belongs
+/// to, nested execution contexts inherit the hash.
+///
+/// # Panics
+///
+/// If there is no execution context.
+pub fn set_tx_hash(hash: Hash) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .exec_contexts
+            .last_mut()
+            .expect("there must be at least one execution context in test environment")
+            .tx_hash = Some(hash);
+    })
+}
+
+/// Sets the chain ID, which is `chain0` by default.
+pub fn set_chain_id(chain_id: &str) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.chain_id = chain_id.to_string();
+    })
+}
+
+/// Sets the group ID, which is `group0` by default.
+pub fn set_group_id(group_id: &str) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.group_id = group_id.to_string();
+    })
+}
+
+/// Sets the gas limit of current transaction, `env::get_gas_left` returns the
+/// gas limit minus the gas consumed since then according to current cost
+/// table.
+pub fn set_gas_limit(gas_limit: u64) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let consumed = instance.gas_usage.gas(&instance.cost_table);
+        instance.gas_limit = (gas_limit, consumed);
+    })
+}
+
+/// The entry points of a contract.
+///
+/// This trait is implemented by `#[liquid::contract]` automatically for the
+/// storage struct of the contract in tests, e.g., a contract whose storage
+/// struct is `Token` can be registered via `register_contract::<Token>(addr)`.
+pub trait ContractEntry {
+    /// Runs the constructor with parameters decoded from the call data.
+    fn deploy();
+
+    /// Dispatches the call data to the corresponding external method.
+    fn dispatch() -> DispatchResult;
+
+    /// Returns the name of the external method whose selector is `selector`.
+    fn method_name(selector: Selector) -> Option<&'static str> {
+        let _ = selector;
+        None
+    }
+}
+
+/// Registers the contract `C` at `addr`.
+///
+/// Calls to `addr` made via `env::call` or interface stubs will be dispatched
+/// to the external methods of `C` since then.
+///
+/// # Note
+///
+/// Every registered contract owns an isolated storage, but the constructor of
+/// `C` is not executed, use [`deploy_contract`] to initialize the state.
+pub fn register_contract<C>(addr: Address)
+where
+    C: ContractEntry,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .contracts
+            .insert(addr, RegisteredContract::of::<C>());
+    })
+}
+
+/// Signs `hash` with the secp256k1 `secret_key`, returns the 65 bytes signature
+/// accepted by `env::ecrecover` and the address of the signer.
+///
+/// # Panics
+///
+/// If `secret_key` is not a valid secp256k1 secret key.
+#[cfg(feature = "off-chain-crypto")]
+pub fn sign_secp256k1(secret_key: &[u8; 32], hash: &Hash) -> (Vec<u8>, Address) {
+    super::crypto::sign_secp256k1(secret_key, hash)
+}
+
+/// Signs `hash` with the SM2 `secret_key`, returns the 64 bytes signature and
+/// the 64 bytes public key accepted by `env::sm2_verify`.
+///
+/// # Panics
+///
+/// If `secret_key` is not a valid SM2 secret key.
+#[cfg(feature = "off-chain-crypto")]
+pub fn sign_sm2(secret_key: &[u8; 32], hash: &Hash) -> (Vec<u8>, Vec<u8>) {
+    super::crypto::sign_sm2(secret_key, hash)
+}
+
+/// Registers the contract `C` as the contract built from `code`.
+///
+/// Contracts can deploy `C` via `env::create`, `env::create2` or the `deploy`
+/// methods of interface stubs with `code` since then, and the new instance
+/// will be registered at the returned address.
+pub fn register_code<C>(code: &[u8])
+where
+    C: ContractEntry,
+{
+    let code_hash = Hash::from(liquid_primitives::hash::hash(code));
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .codes
+            .insert(code_hash, RegisteredContract::of::<C>());
+    })
+}
+
+/// Registers the contract `C` at `addr`, then executes its constructor with
+/// `params` on behalf of current caller in a new call frame.
+///
+/// `params` should be a tuple holding the parameters of the constructor in
+/// order, e.g. `(initial_supply,)`. If the constructor reverts, the contract
+/// is not registered, all changes made by the constructor are discarded and
+/// the revert message is returned.
+pub fn deploy_contract<C, P>(addr: Address, params: P) -> Result<(), Reverted>
+where
+    C: ContractEntry,
+    P: scale::Encode,
+{
+    let deployer = <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .exec_contexts
+            .last()
+            .map(|exec_context| exec_context.caller())
+            .unwrap_or_default()
+    });
+    EnvInstance::execute_frame(|| {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            instance
+                .contracts
+                .insert(addr.clone(), RegisteredContract::of::<C>());
+            instance.exec_contexts.push(ExecContext::with_call_data(
+                deployer,
+                addr,
+                params.encode(),
+            ))
+        });
+        C::deploy();
+        pop_execution_context();
+    })
+}
+
+/// Returns whether there is a contract registered at `addr`.
+pub fn is_contract_registered(addr: &Address) -> bool {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.contracts.contains_key(addr)
+    })
+}
+
+/// The error returned when the execution reverts, which carries the revert
+/// message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reverted(pub String);
+
+impl core::fmt::Display for Reverted {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "execution reverted: {}", self.0)
+    }
+}
+
+/// Executes `f` like a transaction, and returns the revert message as an error
+/// if `f` reverts.
+///
+/// When reverting, all changes made to storage, events and assets during the
+/// execution are rolled back, just like what happens on chain.
+///
+/// # Note
+///
+/// Values cached in storage containers are not written to the test environment
+/// until they are flushed, so the in-memory state of a contract constructed in
+/// unit tests won't be rolled back. Use [`deploy_contract`] and call the
+/// contract via `env::call` to test rollback of contract state.
+pub fn catch_revert<F, R>(f: F) -> Result<R, Reverted>
+where
+    F: FnOnce() -> R,
+{
+    EnvInstance::execute_frame(f)
+}
+
+/// Executes `f` in a new call frame of current contract as if a foreign method
+/// was called, which is static if `is_static` is true.
+///
+/// It's used by the mocks of interfaces to run the functions returning mocked
+/// values, e.g., a mocked method with `&self` receiver fails if its returning
+/// function modifies the state, as the method would be called via a static
+/// call on chain.
+#[doc(hidden)]
+pub fn mock_call<F, R>(is_static: bool, f: F) -> crate::env::error::Result<R>
+where
+    F: FnOnce() -> R,
+{
+    let mut exec_context = <EnvInstance as OnInstance>::on_instance(|instance| {
+        let address = instance.current_address();
+        let mut exec_context = ExecContext::new(address.clone(), address);
+        exec_context.is_static = instance
+            .exec_contexts
+            .last()
+            .map_or(false, |exec_context| exec_context.is_static);
+        exec_context
+    });
+    exec_context.is_static |= is_static;
+    let _guard = enter_exec_context(exec_context);
+    EnvInstance::execute_frame(f)
+        .map_err(|Reverted(msg)| crate::env::error::EnvError::CallReverted(msg))
+}
+
+/// Calls the contract registered at `addr` with raw call data, which consists
+/// of a 4-bytes selector in little endian followed by the encoded parameters.
+///
+/// The call data is routed through the dispatcher generated for the contract.
+/// Returns the bytes passed to `finish` if succeeded, or the bytes passed to
+/// `revert` otherwise, e.g., when the selector is unknown or the parameters
+/// can't be decoded.
+///
+/// # Panics
+///
+/// If there is no contract registered at `addr`.
+pub fn call_raw(addr: &Address, call_data: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+    let (contract, caller) = <EnvInstance as OnInstance>::on_instance(|instance| {
+        let contract = *instance
+            .contracts
+            .get(addr)
+            .unwrap_or_else(|| panic!("no contract is registered at {:?}", addr));
+        let caller = instance
+            .exec_contexts
+            .last()
+            .map(|exec_context| exec_context.caller())
+            .unwrap_or_default();
+        (contract, caller)
+    });
+
+    EnvInstance::invoke(contract, caller, addr.clone(), call_data.to_vec(), false)
+        .map_err(|reverted| scale::Encode::encode(&reverted.0))
+}
+
+/// Returns all registered assets ordered by name.
+pub fn get_assets() -> BTreeMap<String, AssetInfo> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .assets_info
+            .iter()
+            .map(|(name, asset_info)| (name.clone(), asset_info.clone()))
+            .collect()
+    })
+}
+
+/// Returns the information of the asset named `asset_name` if registered.
+pub fn get_asset(asset_name: &str) -> Option<AssetInfo> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.assets_info.get(asset_name).cloned()
+    })
+}
+
+/// Returns the balances of all holders of the fungible asset named
+/// `asset_name`.
+pub fn get_fungible_asset_holders(asset_name: &str) -> BTreeMap<Address, u64> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .fungible_asset
+            .get(asset_name)
+            .map(|holders| {
+                holders
+                    .iter()
+                    .map(|(holder, balance)| (holder.clone(), *balance))
+                    .collect()
+            })
+            .unwrap_or_default()
+    })
+}
+
+/// Returns the tokens owned by all holders of the not fungible asset named
+/// `asset_name`, each token is represented as a pair of ID and URI.
+pub fn get_not_fungible_asset_holders(
+    asset_name: &str,
+) -> BTreeMap<Address, BTreeMap<u64, String>> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .not_fungible_asset
+            .get(asset_name)
+            .map(|holders| {
+                holders
+                    .iter()
+                    .map(|(holder, tokens)| {
+                        let tokens =
+                            tokens.iter().map(|(id, uri)| (*id, uri.clone())).collect();
+                        (holder.clone(), tokens)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    })
+}
+
+/// Gives `amount` of the fungible asset named `asset_name` to `account`
+/// directly, without checking the issuer.
+///
+/// # Panics
+///
+/// If the asset is not a registered fungible asset, or the total supply is
+/// exceeded.
+pub fn seed_fungible_asset(account: &Address, asset_name: &str, amount: u64) {
+    let succeeded = <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.mint_fungible_asset(account, asset_name, amount)
+    });
+    assert!(succeeded, "failed to seed fungible asset `{}`", asset_name);
+}
+
+/// Gives a token of the not fungible asset named `asset_name` to `account`
+/// directly, without checking the issuer. Returns the ID of the token.
+///
+/// # Panics
+///
+/// If the asset is not a registered not fungible asset, or the total supply is
+/// exceeded.
+pub fn seed_not_fungible_asset(account: &Address, asset_name: &str, uri: &str) -> u64 {
+    let id = <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.mint_not_fungible_asset(account, asset_name, uri)
+    });
+    assert_ne!(id, 0, "failed to seed not fungible asset `{}`", asset_name);
+    id
+}
+
+/// The resources consumed by an external method.
+#[derive(Clone, Debug, Default)]
+pub struct MethodGas {
+    /// The number of times the method has been invoked.
+    pub invocations: u64,
+    /// The accumulated resources consumed by all invocations.
+    pub usage: GasUsage,
+    /// The accumulated gas of all invocations according to current cost table.
+    pub gas: u64,
+}
+
+/// Sets the cost table used to calculate gas.
+pub fn set_cost_table(cost_table: CostTable) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.cost_table = cost_table;
+    })
+}
+
+/// Returns the cost table used to calculate gas.
+pub fn get_cost_table() -> CostTable {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.cost_table.clone())
+}
+
+/// Returns the resources consumed since the test environment was initialized.
+pub fn gas_usage() -> GasUsage {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.gas_usage.clone())
+}
+
+/// Executes `f` and returns the resources consumed by it, use
+/// [`GasUsage::gas`] to calculate the gas.
+pub fn metered<F, R>(f: F) -> (R, GasUsage)
+where
+    F: FnOnce() -> R,
+{
+    EnvInstance::metered(f)
+}
+
+/// Returns the resources consumed by each external method invoked via
+/// `env::call` or [`call_raw`], keyed by the address of contract and the name
+/// of method.
+///
+/// The resources consumed by a method include the ones consumed by the nested
+/// calls it makes, and the resources consumed by reverted invocations are
+/// counted as well.
+pub fn gas_report() -> BTreeMap<(Address, String), MethodGas> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .method_gas
+            .iter()
+            .map(|(key, (invocations, usage))| {
+                let method_gas = MethodGas {
+                    invocations: *invocations,
+                    usage: usage.clone(),
+                    gas: usage.gas(&instance.cost_table),
+                };
+                (key.clone(), method_gas)
+            })
+            .collect()
+    })
+}
+
+/// A snapshot of the storage of a contract.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StorageSnapshot {
+    entries: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl StorageSnapshot {
+    /// Returns the encoded value stored at `key`.
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        self.entries.get(key).map(|value| value.as_slice())
+    }
+
+    /// Returns the number of keys in the snapshot.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the snapshot is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns all entries in the snapshot ordered by key.
+    pub fn dump(&self) -> Vec<StorageEntry> {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            self.entries
+                .iter()
+                .map(|(key, value)| StorageEntry {
+                    path: instance.layouts.path_of(key),
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+                .collect()
+        })
+    }
+}
+
+/// An entry in storage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageEntry {
+    /// The readable path decoded from the key, see [`key_path`].
+    pub path: String,
+    /// The raw key.
+    pub key: Vec<u8>,
+    /// The encoded value.
+    pub value: Vec<u8>,
+}
+
+/// A change between two storage snapshots.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StorageChange {
+    /// A key was written.
+    Inserted(StorageEntry),
+    /// A key was removed.
+    Removed(StorageEntry),
+    /// The value stored at a key was changed.
+    Updated {
+        path: String,
+        key: Vec<u8>,
+        old: Vec<u8>,
+        new: Vec<u8>,
+    },
+}
+
+impl StorageChange {
+    /// Returns the readable path of the changed key.
+    pub fn path(&self) -> &str {
+        match self {
+            StorageChange::Inserted(entry) | StorageChange::Removed(entry) => &entry.path,
+            StorageChange::Updated { path, .. } => path,
+        }
+    }
+
+    /// Returns the raw changed key.
+    pub fn key(&self) -> &[u8] {
+        match self {
+            StorageChange::Inserted(entry) | StorageChange::Removed(entry) => &entry.key,
+            StorageChange::Updated { key, .. } => key,
+        }
+    }
+}
+
+/// Returns the readable path of a raw storage key according to the bound
+/// storage containers.
+///
+/// Elements of `storage::Vec` are displayed as `field[index]`, values of
+/// `storage::Mapping` are displayed as `field/0x<encoded key>`, and the length
+/// of these containers is displayed as `field.len`. Keys used by containers
+/// inside other containers are prefixed with the key of the outer ones, e.g.,
+/// `field$keys[3]` and `field$mapping/0x01000000` for `storage::IterableMapping`,
+/// and containers nested in a `storage::Mapping` are displayed as the slot of
+/// the outer one, e.g., `allowances/0x01/0x02`.
+pub fn key_path(key: &[u8]) -> String {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.layouts.path_of(key))
+}
+
+/// Takes a snapshot of the storage of current contract.
+pub fn storage_snapshot() -> StorageSnapshot {
+    let address =
+        <EnvInstance as OnInstance>::on_instance(|instance| instance.current_address());
+    storage_snapshot_of(&address)
+}
+
+/// Takes a snapshot of the storage of the contract at `addr`.
+pub fn storage_snapshot_of(addr: &Address) -> StorageSnapshot {
+    <EnvInstance as OnInstance>::on_instance(|instance| StorageSnapshot {
+        entries: instance
+            .contract_storages
+            .get(addr)
+            .map(|storage| storage.entries().clone())
+            .unwrap_or_default(),
+    })
+}
+
+/// Returns all entries in the storage of current contract ordered by key.
+pub fn dump_storage() -> Vec<StorageEntry> {
+    storage_snapshot().dump()
+}
+
+/// Returns the changes from `before` to `after` ordered by key.
+pub fn storage_diff(
+    before: &StorageSnapshot,
+    after: &StorageSnapshot,
+) -> Vec<StorageChange> {
+    let entry = |key: &Vec<u8>, value: &Vec<u8>| StorageEntry {
+        path: key_path(key),
+        key: key.clone(),
+        value: value.clone(),
+    };
+
+    let mut changes = Vec::new();
+    for (key, old) in &before.entries {
+        match after.entries.get(key) {
+            None => changes.push(StorageChange::Removed(entry(key, old))),
+            Some(new) if new != old => changes.push(StorageChange::Updated {
+                path: key_path(key),
+                key: key.clone(),
+                old: old.clone(),
+                new: new.clone(),
+            }),
+            _ => (),
+        }
+    }
+    for (key, new) in &after.entries {
+        if !before.entries.contains_key(key) {
+            changes.push(StorageChange::Inserted(entry(key, new)));
+        }
+    }
+    changes.sort_by(|a, b| a.key().cmp(b.key()));
+    changes
+}
@@ -10,9 +10,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::env::error::{EnvError, Result};
+
+/// A buffer used to exchange small data with the host.
+///
+/// Data which doesn't fit in the buffer should be exchanged through heap
+/// allocated memory instead, hence writing too many bytes into the buffer
+/// only marks the buffer as overflowed rather than trapping.
 pub struct StaticBuffer {
     buffer: [u8; Self::CAPACITY],
     len: usize,
+    overflowed: bool,
 }
 
 impl StaticBuffer {
@@ -23,6 +31,7 @@ impl StaticBuffer {
         Self {
             buffer: [0; Self::CAPACITY],
             len: 0,
+            overflowed: false,
         }
     }
 
@@ -34,11 +43,27 @@ impl StaticBuffer {
     #[inline(always)]
     pub fn clear(&mut self) {
         self.len = 0;
+        self.overflowed = false;
+    }
+
+    /// Returns whether some bytes were discarded since the last `clear`
+    /// because there was not enough space.
+    #[inline(always)]
+    pub fn is_overflowed(&self) -> bool {
+        self.overflowed
+    }
+
+    /// Returns the whole buffer regardless of its length, which is used as the
+    /// destination of data written by the host.
+    #[inline(always)]
+    pub fn as_mut_bytes(&mut self) -> &mut [u8] {
+        &mut self.buffer[..]
     }
 
     pub fn write_bytes(&mut self, bytes: &[u8]) {
-        if self.len + bytes.len() > Self::CAPACITY {
-            panic!("static buffer overflowed");
+        if self.overflowed || self.len + bytes.len() > Self::CAPACITY {
+            self.overflowed = true;
+            return;
         }
 
         let start = self.len;
@@ -47,11 +72,48 @@ impl StaticBuffer {
         self.len += bytes_len;
     }
 
-    pub fn resize(&mut self, new_len: usize) {
+    pub fn resize(&mut self, new_len: usize) -> Result<()> {
         if new_len > Self::CAPACITY {
-            panic!("static buffer overflowed");
+            return Err(EnvError::NotEnoughSpace);
         }
         self.len = new_len;
+        Ok(())
+    }
+
+    /// Encodes `value` into the buffer and returns the encoded bytes, or
+    /// `NotEnoughSpace` if the encoded value doesn't fit in the buffer.
+    pub fn encode<V>(&mut self, value: &V) -> Result<&[u8]>
+    where
+        V: scale::Encode,
+    {
+        self.clear();
+        if scale::Encode::size_hint(value) <= Self::CAPACITY {
+            scale::Encode::encode_to(value, self);
+            if !self.is_overflowed() {
+                return Ok(&self[..]);
+            }
+        }
+        self.clear();
+        Err(EnvError::NotEnoughSpace)
+    }
+
+    /// Encodes `value` and passes the encoded bytes to `f`.
+    ///
+    /// The value is encoded into the buffer if it fits in, otherwise it's
+    /// encoded into heap allocated memory.
+    pub fn with_encoded<V, F, T>(&mut self, value: &V, f: F) -> T
+    where
+        V: scale::Encode,
+        F: FnOnce(&[u8]) -> T,
+    {
+        if scale::Encode::size_hint(value) <= Self::CAPACITY {
+            self.clear();
+            scale::Encode::encode_to(value, self);
+            if !self.is_overflowed() {
+                return f(&self[..]);
+            }
+        }
+        f(&scale::Encode::encode(value))
     }
 }
 
@@ -78,3 +140,115 @@ impl<I: core::slice::SliceIndex<[u8]>> core::ops::IndexMut<I> for StaticBuffer {
         core::ops::IndexMut::index_mut(&mut self.buffer[..self.len], index)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use liquid_prelude::vec::Vec;
+
+    #[test]
+    fn write_bytes_works() {
+        let mut buffer = StaticBuffer::new();
+        buffer.write_bytes(&[1, 2, 3]);
+        buffer.write_bytes(&[4]);
+        assert_eq!(buffer.len(), 4);
+        assert_eq!(&buffer[..], &[1, 2, 3, 4]);
+        assert!(!buffer.is_overflowed());
+    }
+
+    #[test]
+    fn write_bytes_overflow() {
+        let mut buffer = StaticBuffer::new();
+        buffer.write_bytes(&[0; StaticBuffer::CAPACITY - 1]);
+        assert!(!buffer.is_overflowed());
+
+        buffer.write_bytes(&[1, 2]);
+        assert!(buffer.is_overflowed());
+        assert_eq!(buffer.len(), StaticBuffer::CAPACITY - 1);
+
+        // Once overflowed, the buffer discards every following write, even
+        // those which would fit in.
+        buffer.write_bytes(&[3]);
+        assert!(buffer.is_overflowed());
+        assert_eq!(buffer.len(), StaticBuffer::CAPACITY - 1);
+
+        buffer.clear();
+        assert!(!buffer.is_overflowed());
+        buffer.write_bytes(&[3]);
+        assert_eq!(&buffer[..], &[3]);
+    }
+
+    #[test]
+    fn resize_works() {
+        let mut buffer = StaticBuffer::new();
+        buffer.as_mut_bytes()[..3].copy_from_slice(&[1, 2, 3]);
+        assert!(buffer.resize(3).is_ok());
+        assert_eq!(&buffer[..], &[1, 2, 3]);
+        assert!(buffer.resize(StaticBuffer::CAPACITY).is_ok());
+        assert_eq!(buffer.len(), StaticBuffer::CAPACITY);
+    }
+
+    #[test]
+    fn resize_not_enough_space() {
+        let mut buffer = StaticBuffer::new();
+        buffer.write_bytes(&[1, 2, 3]);
+        assert!(matches!(
+            buffer.resize(StaticBuffer::CAPACITY + 1),
+            Err(EnvError::NotEnoughSpace)
+        ));
+        assert_eq!(&buffer[..], &[1, 2, 3]);
+    }
+
+    #[test]
+    fn with_encoded_uses_buffer() {
+        let mut buffer = StaticBuffer::new();
+        let value = (1u32, 2u64);
+        let encoded = buffer.with_encoded(&value, |encoded| encoded.to_vec());
+        assert_eq!(encoded, scale::Encode::encode(&value));
+        assert_eq!(buffer.len(), encoded.len());
+        assert!(!buffer.is_overflowed());
+    }
+
+    #[test]
+    fn with_encoded_falls_back_to_heap() {
+        let mut buffer = StaticBuffer::new();
+        let value: Vec<u8> = (0..StaticBuffer::CAPACITY * 2).map(|i| i as u8).collect();
+        let encoded = buffer.with_encoded(&value, |encoded| encoded.to_vec());
+        assert_eq!(encoded, scale::Encode::encode(&value));
+        assert!(encoded.len() > StaticBuffer::CAPACITY);
+
+        // The buffer is left usable for small values afterwards.
+        let encoded = buffer.with_encoded(&42u8, |encoded| encoded.to_vec());
+        assert_eq!(encoded, [42]);
+    }
+
+    #[test]
+    fn encode_works() {
+        let mut buffer = StaticBuffer::new();
+        let value = (1u32, 2u64);
+        assert_eq!(
+            buffer.encode(&value).ok(),
+            Some(&scale::Encode::encode(&value)[..])
+        );
+
+        // The encoded value may take exactly the whole buffer.
+        let value: Vec<u8> = vec![7; StaticBuffer::CAPACITY - 2];
+        let encoded = buffer.encode(&value).ok().map(<[u8]>::to_vec);
+        assert_eq!(encoded, Some(scale::Encode::encode(&value)));
+        assert_eq!(buffer.len(), StaticBuffer::CAPACITY);
+    }
+
+    #[test]
+    fn encode_over_capacity() {
+        let mut buffer = StaticBuffer::new();
+        let value: Vec<u8> = vec![7; StaticBuffer::CAPACITY];
+        assert!(matches!(
+            buffer.encode(&value),
+            Err(EnvError::NotEnoughSpace)
+        ));
+        assert_eq!(buffer.len(), 0);
+        assert!(!buffer.is_overflowed());
+
+        assert_eq!(buffer.encode(&42u8).ok(), Some(&[42u8][..]));
+    }
+}
@@ -53,6 +53,37 @@ mod sys {
 
         pub fn getBlockNumber() -> u64;
 
+        // The following imports marked with `unstable-host-api` are not
+        // exported by released FISCO BCOS nodes, a contract importing any of
+        // them fails at instantiation on such nodes. The ABI they assume is
+        // documented on each of them, in which an offset is an address in the
+        // linear memory of the contract and a length is in bytes.
+
+        /// Writes the 32 bytes hash of current transaction to `result_offset`.
+        #[cfg(feature = "unstable-host-api")]
+        pub fn getTxHash(result_offset: u32);
+
+        /// Writes the 32 bytes hash of the block numbered `block_number` to
+        /// `result_offset` and returns 32, or writes nothing and returns 0 if
+        /// the block is not one of the 256 most recent blocks before current
+        /// block.
+        #[cfg(feature = "unstable-host-api")]
+        pub fn getBlockHash(block_number: u64, result_offset: u32) -> u32;
+
+        /// Writes the chain ID in UTF-8 to `result_offset` and returns its
+        /// length, which must not exceed 16KB.
+        #[cfg(feature = "unstable-host-api")]
+        pub fn getChainId(result_offset: u32) -> u32;
+
+        /// Writes the group ID in UTF-8 to `result_offset` and returns its
+        /// length, which must not exceed 16KB.
+        #[cfg(feature = "unstable-host-api")]
+        pub fn getGroupId(result_offset: u32) -> u32;
+
+        /// Returns the remaining gas of current transaction.
+        #[cfg(feature = "unstable-host-api")]
+        pub fn getGasLeft() -> u64;
+
         pub fn call(
             address_offset: u32,
             address_length: u32,
@@ -60,6 +91,18 @@ mod sys {
             data_length: u32,
         ) -> u32;
 
+        /// Same as `call`, i.e., returns 0 on success or a non-zero status on
+        /// failure with the return data available via `getReturnDataSize` and
+        /// `getReturnData`, but the callee and its nested calls fail whenever
+        /// they modify the state.
+        #[cfg(feature = "unstable-host-api")]
+        pub fn staticCall(
+            address_offset: u32,
+            address_length: u32,
+            data_offset: u32,
+            data_length: u32,
+        ) -> u32;
+
         pub fn getReturnDataSize() -> u32;
 
         pub fn getReturnData(result_offset: u32);
@@ -259,6 +302,32 @@ pub fn get_block_number() -> u64 {
     unsafe { sys::getBlockNumber() }
 }
 
+#[cfg(feature = "unstable-host-api")]
+pub fn get_tx_hash(result_offset: &mut [u8]) {
+    unsafe { sys::getTxHash(result_offset.as_mut_ptr() as u32) }
+}
+
+/// Returns the length of the block hash, or 0 if the block is unavailable.
+#[cfg(feature = "unstable-host-api")]
+pub fn get_block_hash(block_number: u64, result_offset: &mut [u8]) -> u32 {
+    unsafe { sys::getBlockHash(block_number, result_offset.as_mut_ptr() as u32) }
+}
+
+#[cfg(feature = "unstable-host-api")]
+pub fn get_chain_id(result_offset: &mut [u8]) -> u32 {
+    unsafe { sys::getChainId(result_offset.as_mut_ptr() as u32) }
+}
+
+#[cfg(feature = "unstable-host-api")]
+pub fn get_group_id(result_offset: &mut [u8]) -> u32 {
+    unsafe { sys::getGroupId(result_offset.as_mut_ptr() as u32) }
+}
+
+#[cfg(feature = "unstable-host-api")]
+pub fn get_gas_left() -> u64 {
+    unsafe { sys::getGasLeft() }
+}
+
 pub fn call(address: &[u8], data: &[u8]) -> u32 {
     unsafe {
         sys::call(
@@ -270,6 +339,18 @@ pub fn call(address: &[u8], data: &[u8]) -> u32 {
     }
 }
 
+#[cfg(feature = "unstable-host-api")]
+pub fn static_call(address: &[u8], data: &[u8]) -> u32 {
+    unsafe {
+        sys::staticCall(
+            address.as_ptr() as u32,
+            address.len() as u32,
+            data.as_ptr() as u32,
+            data.len() as u32,
+        )
+    }
+}
+
 pub fn get_return_data_size() -> u32 {
     unsafe { sys::getReturnDataSize() }
 }
@@ -15,15 +15,21 @@ pub mod ext;
 
 use self::buffer::StaticBuffer;
 use super::OnInstance;
-use crate::env::{
-    backend::Env,
-    calldata::CallData,
-    error::{EnvError, Result},
-    CallMode,
+use crate::{
+    env::{
+        backend::{Env, ReentrantEnv},
+        calldata::CallData,
+        error::{EnvError, Result},
+        CallMode,
+    },
+    precompiled::Crypto,
 };
 use core::convert::TryInto;
 use liquid_prelude::{string::String, vec::Vec};
-use liquid_primitives::{types::Address, Topics};
+use liquid_primitives::{
+    types::{Address, Hash},
+    Topics,
+};
 
 /// The on-chain environment
 pub struct EnvInstance {
@@ -44,16 +50,13 @@ impl OnInstance for EnvInstance {
 }
 
 impl EnvInstance {
-    fn reset_buffer(&mut self) {
-        self.buffer.clear();
-    }
-
-    fn encode_into_buffer_scale<V>(&mut self, value: &V)
+    /// Encodes `value` and passes the encoded bytes to `f`.
+    fn with_encoded<V, F, T>(&mut self, value: &V, f: F) -> T
     where
         V: scale::Encode,
+        F: FnOnce(&[u8]) -> T,
     {
-        self.reset_buffer();
-        scale::Encode::encode_to(value, &mut self.buffer);
+        self.buffer.with_encoded(value, f)
     }
 
     fn decode_from_buffer_scale<R>(&mut self) -> Result<R>
@@ -63,6 +66,70 @@ impl EnvInstance {
         let len = self.buffer.len();
         scale::Decode::decode(&mut &self.buffer[..len]).map_err(Into::into)
     }
+
+    /// Lets `read` write `size` bytes of data from the host and decodes them.
+    ///
+    /// The static buffer is used if the data fits in, otherwise a buffer with
+    /// exactly `size` bytes will be allocated on heap.
+    fn read_and_decode<R, F>(&mut self, size: u32, read: F) -> Result<R>
+    where
+        R: scale::Decode,
+        F: FnOnce(&mut [u8]) -> Result<()>,
+    {
+        let size = size as usize;
+        if size <= StaticBuffer::CAPACITY {
+            if size != 0 {
+                read(self.buffer.as_mut_bytes())?;
+            }
+            self.buffer.resize(size)?;
+            self.decode_from_buffer_scale()
+        } else {
+            let mut heap_buffer = liquid_prelude::vec::from_elem(0u8, size);
+            read(&mut heap_buffer)?;
+            scale::Decode::decode(&mut heap_buffer.as_slice()).map_err(Into::into)
+        }
+    }
+
+    /// Reads the result of a cross-contract call whose status is `status`.
+    ///
+    /// If the call failed, the return data is the revert message of the callee.
+    fn read_call_result<R>(&mut self, status: u32) -> Result<R>
+    where
+        R: scale::Decode,
+    {
+        // TODO(#1): Optimize the performance of getting return data size
+        let read_return_data = |buffer: &mut [u8]| {
+            ext::get_return_data(buffer);
+            Ok(())
+        };
+        if status != 0 {
+            let return_data_size = ext::get_return_data_size();
+            if return_data_size == 0 {
+                return Err(EnvError::FailToCallForeignContract);
+            }
+            return match self
+                .read_and_decode::<String, _>(return_data_size, read_return_data)
+            {
+                Ok(msg) => Err(EnvError::CallReverted(msg)),
+                Err(_) => Err(EnvError::FailToCallForeignContract),
+            };
+        }
+
+        if core::mem::size_of::<R>() == 0 {
+            // The `R` is unit type.
+            self.buffer.clear();
+            self.decode_from_buffer_scale()
+        } else {
+            let return_data_size = ext::get_return_data_size();
+            self.read_and_decode(return_data_size, read_return_data)
+        }
+    }
+
+    /// Reads a string with `size` bytes written by the host from the buffer.
+    fn read_string(&mut self, size: u32) -> Result<String> {
+        self.buffer.resize(size as usize)?;
+        Ok(String::from_utf8_lossy(&self.buffer[..]).into_owned())
+    }
 }
 
 impl Env for EnvInstance {
@@ -70,16 +137,28 @@ impl Env for EnvInstance {
     where
         V: scale::Encode,
     {
-        self.encode_into_buffer_scale(value);
-        ext::set_storage(key, &self.buffer[..]);
+        // `getStorage` copies a value into the buffer before reporting its
+        // length, so a value is only stored if it can be read back through the
+        // buffer.
+        match self.buffer.encode(value) {
+            Ok(encoded) => ext::set_storage(key, encoded),
+            Err(_) => {
+                let msg = String::from(
+                    "the encoded value is too large to be stored, the limit is 16KB",
+                );
+                ext::revert(&scale::Encode::encode(&msg));
+            }
+        }
     }
 
     fn get_storage<R>(&mut self, key: &[u8]) -> Result<R>
     where
         R: scale::Decode,
     {
-        let size = ext::get_storage(key, &mut self.buffer[..])?;
-        self.buffer.resize(size as usize);
+        // Values are never stored unless they fit in the buffer, see
+        // `set_storage`.
+        let size = ext::get_storage(key, self.buffer.as_mut_bytes())?;
+        self.buffer.resize(size as usize)?;
         self.decode_from_buffer_scale()
     }
 
@@ -116,40 +195,8 @@ impl Env for EnvInstance {
     where
         Event: Topics + scale::Encode,
     {
-        self.encode_into_buffer_scale(&event);
         let topics = event.topics();
-        ext::log(&self.buffer[..self.buffer.len()], &topics);
-    }
-
-    fn call<R>(&mut self, addr: &Address, data: &[u8]) -> Result<R>
-    where
-        R: scale::Decode,
-    {
-        let status = ext::call(addr.as_bytes(), data);
-        if status != 0 {
-            return Err(EnvError::FailToCallForeignContract);
-        }
-        if core::mem::size_of::<R>() == 0 {
-            // The `R` is unit type.
-            self.buffer.clear();
-            self.decode_from_buffer_scale()
-        } else {
-            // TODO(#1): Optimize the performance of getting return data size
-            let return_data_size = ext::get_return_data_size();
-            if return_data_size <= StaticBuffer::CAPACITY as u32 {
-                if return_data_size != 0 {
-                    ext::get_return_data(&mut self.buffer[..]);
-                }
-                self.buffer.resize(return_data_size as usize);
-                self.decode_from_buffer_scale()
-            } else {
-                let mut return_data_buffer =
-                    liquid_prelude::vec::from_elem(0u8, return_data_size as usize);
-                ext::get_return_data(&mut return_data_buffer);
-                scale::Decode::decode(&mut return_data_buffer.as_slice())
-                    .map_err(Into::into)
-            }
-        }
+        self.with_encoded(&event, |encoded| ext::log(encoded, &topics));
     }
 
     fn finish<V>(&mut self, return_value: &V)
@@ -169,27 +216,18 @@ impl Env for EnvInstance {
     }
 
     fn get_caller(&mut self) -> Address {
-        let size = ext::get_caller(&mut self.buffer[..]);
-        self.buffer.resize(size as usize);
-        String::from_utf8_lossy(&self.buffer[..size as usize])
-            .into_owned()
-            .into()
+        let size = ext::get_caller(self.buffer.as_mut_bytes());
+        self.read_string(size).unwrap().into()
     }
 
     fn get_tx_origin(&mut self) -> Address {
-        let size = ext::get_tx_origin(&mut self.buffer[..]);
-        self.buffer.resize(size as usize);
-        String::from_utf8_lossy(&self.buffer[..size as usize])
-            .into_owned()
-            .into()
+        let size = ext::get_tx_origin(self.buffer.as_mut_bytes());
+        self.read_string(size).unwrap().into()
     }
 
     fn get_address(&mut self) -> Address {
-        let size = ext::get_address(&mut self.buffer[..]);
-        self.buffer.resize(size as usize);
-        String::from_utf8_lossy(&self.buffer[..size as usize])
-            .into_owned()
-            .into()
+        let size = ext::get_address(self.buffer.as_mut_bytes());
+        self.read_string(size).unwrap().into()
     }
 
     fn now(&mut self) -> u64 {
@@ -200,6 +238,39 @@ impl Env for EnvInstance {
         ext::get_block_number() as u64
     }
 
+    #[cfg(feature = "unstable-host-api")]
+    fn get_tx_hash(&mut self) -> Hash {
+        let mut hash = [0u8; 32];
+        ext::get_tx_hash(&mut hash);
+        hash.into()
+    }
+
+    #[cfg(feature = "unstable-host-api")]
+    fn get_block_hash(&mut self, block_number: u64) -> Option<Hash> {
+        let mut hash = [0u8; 32];
+        match ext::get_block_hash(block_number, &mut hash) {
+            32 => Some(hash.into()),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "unstable-host-api")]
+    fn get_chain_id(&mut self) -> String {
+        let size = ext::get_chain_id(self.buffer.as_mut_bytes());
+        self.read_string(size).unwrap()
+    }
+
+    #[cfg(feature = "unstable-host-api")]
+    fn get_group_id(&mut self) -> String {
+        let size = ext::get_group_id(self.buffer.as_mut_bytes());
+        self.read_string(size).unwrap()
+    }
+
+    #[cfg(feature = "unstable-host-api")]
+    fn get_gas_left(&mut self) -> u64 {
+        ext::get_gas_left()
+    }
+
     fn get_external_code_size(&self, account: &Address) -> u32 {
         ext::get_external_code_size(account.as_bytes())
     }
@@ -257,13 +328,12 @@ impl Env for EnvInstance {
             account.as_bytes(),
             asset_name,
             asset_id,
-            &mut self.buffer[..],
+            self.buffer.as_mut_bytes(),
         );
         if size == 0 {
             return String::new();
         }
-        self.buffer.resize(size as usize);
-        String::from_utf8(self.buffer[..].to_vec()).unwrap()
+        self.read_string(size).unwrap()
     }
 
     fn get_not_fungible_asset_ids(
@@ -272,12 +342,13 @@ impl Env for EnvInstance {
         asset_name: &[u8],
     ) -> Vec<u64> {
         let mut ret = Vec::new();
-        if let Ok(size) = ext::get_not_fungible_asset_ids(
+        let size = ext::get_not_fungible_asset_ids(
             account.as_bytes(),
             asset_name,
-            &mut self.buffer[..],
-        ) {
-            self.buffer.resize(size as usize);
+            self.buffer.as_mut_bytes(),
+        )
+        .and_then(|size| self.buffer.resize(size as usize).map(|_| size));
+        if let Ok(size) = size {
             let mut start: usize = 0;
             while start < size as usize {
                 ret.push(u64::from_le_bytes(
@@ -292,3 +363,34 @@ impl Env for EnvInstance {
         ret
     }
 }
+
+impl ReentrantEnv for EnvInstance {
+    fn call<R>(addr: &Address, data: &[u8]) -> Result<R>
+    where
+        R: scale::Decode,
+    {
+        <Self as OnInstance>::on_instance(|instance| {
+            let status = ext::call(addr.as_bytes(), data);
+            instance.read_call_result(status)
+        })
+    }
+
+    #[cfg(feature = "unstable-host-api")]
+    fn call_static<R>(addr: &Address, data: &[u8]) -> Result<R>
+    where
+        R: scale::Decode,
+    {
+        <Self as OnInstance>::on_instance(|instance| {
+            let status = ext::static_call(addr.as_bytes(), data);
+            instance.read_call_result(status)
+        })
+    }
+
+    fn ecrecover(hash: &Hash, signature: &[u8]) -> Option<Address> {
+        Crypto::ecrecover(hash, signature)
+    }
+
+    fn sm2_verify(hash: &Hash, signature: &[u8], public_key: &[u8]) -> Option<Address> {
+        Crypto::sm2_verify(hash, signature, public_key)
+    }
+}
@@ -11,6 +11,7 @@
 // limitations under the License.
 
 use derive_more::From;
+use liquid_prelude::string::String;
 
 #[derive(From)]
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -21,6 +22,15 @@ pub enum EnvError {
     UnableToReadFromStorage,
     UnableToReadCallData,
     FailToCallForeignContract,
+    /// The callee of a cross-contract call reverted with the message.
+    ///
+    /// The callee only passes the encoded message to the host when reverting,
+    /// hence failures of dispatching, e.g., an unknown selector or invalid
+    /// parameters, can't be told from reverts of the callee itself except by
+    /// the message.
+    #[from(ignore)]
+    CallReverted(String),
+    FailToCreateContract,
 }
 
 /// A result of environmental operations
@@ -19,12 +19,16 @@ pub(crate) mod calldata;
 pub mod engine;
 pub mod error;
 
+#[cfg(any(feature = "std", feature = "unstable-host-api"))]
+pub use self::api::call_static;
+#[cfg(feature = "std")]
+pub use self::api::{create, create2};
 pub use self::{
     api::{
-        call, emit, finish, get_address, get_asset_balance, get_call_data, get_caller,
-        get_external_code_size, get_not_fungible_asset_ids, get_not_fungible_asset_info,
-        issue_fungible_asset, issue_not_fungible_asset, now, register_asset, revert,
-        transfer_asset,
+        call, ecrecover, emit, finish, get_address, get_asset_balance, get_call_data,
+        get_caller, get_external_code_size, get_not_fungible_asset_ids,
+        get_not_fungible_asset_info, issue_fungible_asset, issue_not_fungible_asset, now,
+        register_asset, revert, secp256k1_verify, sm2_verify, transfer_asset,
     },
     backend::CallMode,
 };
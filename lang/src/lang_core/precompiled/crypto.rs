@@ -0,0 +1,146 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::{
+    env::call,
+    precompiled::{ReturnDataWrapper, CRYPTO_ADDRESS, ECRECOVER_ADDRESS},
+};
+use liquid_prelude::{string::String, vec::Vec};
+use liquid_primitives::types::{Address, Hash};
+use scale::{Decode, Encode};
+
+pub struct Crypto;
+
+impl Crypto {
+    /// Recovers the signer from the secp256k1 `signature` of `hash` via the
+    /// `ecRecover` precompiled contract. The signature consists of `r`, `s`
+    /// and the recovery id `v`.
+    ///
+    /// The precompiled contract at `0x1` is the one ported from Ethereum by
+    /// FISCO BCOS, which is called with raw bytes rather than the SCALE codec:
+    /// its input is `hash`, `v`, `r` and `s`, each padded to 32 bytes, and its
+    /// output is the 20 bytes address left padded to 32 bytes, or nothing if
+    /// the signature is invalid. `ReturnDataWrapper` takes the output as is.
+    pub fn ecrecover(hash: &Hash, signature: &[u8]) -> Option<Address> {
+        if signature.len() != 65 {
+            return None;
+        }
+
+        // The input consists of `hash`, `v`, `r` and `s`, each of which is
+        // padded to 32 bytes.
+        let v = signature[64];
+        let mut input_data = Vec::with_capacity(128);
+        input_data.extend(hash.encode());
+        input_data.extend_from_slice(&[0u8; 31]);
+        input_data.push(if v < 27 { v + 27 } else { v });
+        input_data.extend_from_slice(&signature[..64]);
+        let ret = call::<ReturnDataWrapper>(&ECRECOVER_ADDRESS, &input_data).ok()?;
+        if ret.data.len() != 32 || ret.data[12..].iter().all(|byte| *byte == 0) {
+            return None;
+        }
+
+        let mut addr = String::from("0x");
+        addr.push_str(&crate::bytes_to_hex(&ret.data[12..]));
+        Some(addr.into())
+    }
+
+    /// Verifies the SM2 `signature` of `hash` with the uncompressed
+    /// `public_key` via the `sm2Verify` method of the crypto precompiled
+    /// contract. The signature consists of `r` and `s`.
+    pub fn sm2_verify(
+        hash: &Hash,
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Option<Address> {
+        if signature.len() != 64 {
+            return None;
+        }
+
+        let mut input_data = if cfg!(feature = "gm") {
+            [0x22, 0xed, 0xe6, 0x1e]
+        } else {
+            [0xcb, 0xdb, 0x3a, 0x67]
+        }
+        .to_vec();
+        let r = Hash::from(signature[..32].to_vec());
+        let s = Hash::from(signature[32..].to_vec());
+        input_data.extend(&(hash, public_key, r, s).encode());
+        let ret = call::<ReturnDataWrapper>(&CRYPTO_ADDRESS, &input_data).ok()?;
+        match <(bool, Address) as Decode>::decode(&mut ret.data.as_slice()) {
+            Ok((true, addr)) => Some(addr),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{
+        lang_core::env::{self, test, CallMode},
+        DispatchResult,
+    };
+    use std::cell::RefCell;
+
+    thread_local!(static INPUT: RefCell<Vec<u8>> = RefCell::new(Vec::new()));
+
+    /// Records its input, and returns the padded address `0xabab...ab` if
+    /// `v` is 27 or nothing otherwise.
+    struct EcRecover;
+
+    impl test::ContractEntry for EcRecover {
+        fn deploy() {}
+
+        fn dispatch() -> DispatchResult {
+            let input = env::get_call_data(CallMode::Deploy).unwrap().data;
+            let v = input[63];
+            INPUT.with(|recorded| *recorded.borrow_mut() = input);
+            if v == 27 {
+                let mut output = [0u8; 32];
+                output[12..].copy_from_slice(&[0xab; 20]);
+                env::finish(&output);
+            } else {
+                env::finish(&());
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn ecrecover_input_and_output() {
+        let accounts = test::default_accounts();
+        test::register_contract::<EcRecover>(ECRECOVER_ADDRESS.clone());
+        let _guard = test::as_caller(accounts.alice);
+
+        let hash = Hash::from([1u8; 32]);
+        let mut signature = [2u8; 65];
+        signature[32..64].copy_from_slice(&[3u8; 32]);
+        signature[64] = 0;
+        let signer = Address::from("0xabababababababababababababababababababab");
+        assert_eq!(Crypto::ecrecover(&hash, &signature), Some(signer));
+
+        let mut expected = vec![1u8; 32];
+        expected.extend_from_slice(&[0u8; 31]);
+        expected.push(27);
+        expected.extend_from_slice(&[2u8; 32]);
+        expected.extend_from_slice(&[3u8; 32]);
+        INPUT.with(|input| assert_eq!(*input.borrow(), expected));
+
+        // `v` is passed as is if it's already offset by 27, and an empty
+        // output means the signature is invalid.
+        signature[64] = 28;
+        assert_eq!(Crypto::ecrecover(&hash, &signature), None);
+        INPUT.with(|input| assert_eq!(input.borrow()[63], 28));
+
+        assert_eq!(Crypto::ecrecover(&hash, &signature[..64]), None);
+    }
+}
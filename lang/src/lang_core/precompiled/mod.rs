@@ -11,14 +11,19 @@
 // limitations under the License.
 
 mod bfs;
+mod crypto;
 
 pub use bfs::Bfs;
+pub use crypto::Crypto;
 use lazy_static::lazy_static;
 use liquid_prelude::vec::{self, Vec};
 use liquid_primitives::types::Address;
 
 lazy_static! {
     pub static ref BFS_ADDRESS: Address = "/sys/bfs".into();
+    pub static ref CRYPTO_ADDRESS: Address = "/sys/crypto_tools".into();
+    pub static ref ECRECOVER_ADDRESS: Address =
+        "0x0000000000000000000000000000000000000001".into();
 }
 
 struct ReturnDataWrapper {
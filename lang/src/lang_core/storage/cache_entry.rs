@@ -10,12 +10,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use liquid_prelude::boxed::Box;
+use liquid_prelude::{boxed::Box, vec::Vec};
+use scale::Encode;
 
 #[cfg_attr(feature = "std", derive(Debug))]
 pub struct CacheEntry<T> {
     /// If the entry needs to be written back upon a flush.
     dirty: bool,
+    /// The encoded value before the entry was mutably accessed for the first
+    /// time since it was synchronized with the contract storage.
+    origin: Option<Vec<u8>>,
     /// The value of the cell.
     cell_val: Box<Option<T>>,
 }
@@ -24,6 +28,7 @@ impl<T> CacheEntry<T> {
     pub fn new(val: Option<T>) -> Self {
         Self {
             dirty: false,
+            origin: None,
             cell_val: Box::new(val),
         }
     }
@@ -38,6 +43,7 @@ impl<T> CacheEntry<T> {
 
     pub fn mark_clean(&mut self) {
         self.dirty = false;
+        self.origin = None;
     }
 
     /// Returns an immutable reference to the synchronized cached value.
@@ -75,3 +81,37 @@ impl<T> CacheEntry<T> {
         self.put(None)
     }
 }
+
+impl<T> CacheEntry<T>
+where
+    T: Encode,
+{
+    /// Returns a mutable reference to the synchronized cached value.
+    ///
+    /// Different from `get_mut`, the encoded value is recorded before a clean
+    /// entry is marked as dirty, so that the value won't be written back upon
+    /// a flush if it is not actually changed.
+    pub fn get_mut_tracked(&mut self) -> Option<&mut T> {
+        if !self.dirty {
+            self.origin = (*self.cell_val).as_ref().map(Encode::encode);
+        }
+        self.get_mut()
+    }
+
+    /// Returns the value which needs to be written back upon a flush, i.e.,
+    /// the value of a dirty entry whose encoding differs from the recorded
+    /// one, if any.
+    pub fn get_changed(&self) -> Option<&T> {
+        if !self.dirty {
+            return None;
+        }
+
+        let val = (*self.cell_val).as_ref()?;
+        match &self.origin {
+            Some(origin) if val.using_encoded(|encoded| encoded == origin.as_slice()) => {
+                None
+            }
+            _ => Some(val),
+        }
+    }
+}
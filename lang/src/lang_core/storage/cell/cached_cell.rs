@@ -67,10 +67,29 @@ impl<T> Cache<T> {
         }
     }
 
+    pub fn take(&mut self) -> Option<T> {
+        match self {
+            Cache::Desync => panic!("Error: tried to take the value from a desync cache"),
+            Cache::Sync(entry) => entry.take(),
+        }
+    }
+}
+
+impl<T> Cache<T>
+where
+    T: scale::Encode,
+{
     pub fn get_mut(&mut self) -> Option<&mut T> {
         match self {
             Cache::Desync => panic!("Error: tried to get the value from a desync cache"),
-            Cache::Sync(sync_entry) => sync_entry.get_mut(),
+            Cache::Sync(sync_entry) => sync_entry.get_mut_tracked(),
+        }
+    }
+
+    pub fn get_changed(&self) -> Option<&T> {
+        match self {
+            Cache::Desync => None,
+            Cache::Sync(entry) => entry.get_changed(),
         }
     }
 }
@@ -79,6 +98,8 @@ impl<T> Cache<T> {
 pub struct CachedCell<T> {
     cell: TypedCell<T>,
     cache: RefCell<Cache<T>>,
+    /// Produces the value of the cell when nothing is stored in it.
+    default: Option<fn() -> T>,
 }
 
 impl<T> CachedCell<T> {
@@ -86,8 +107,35 @@ impl<T> CachedCell<T> {
         Self {
             cell: TypedCell::new(key),
             cache: Default::default(),
+            default: None,
         }
     }
+
+    /// Creates a cell which is regarded as holding `default()` when nothing
+    /// is stored in it. The default value is not written back to storage
+    /// unless it is mutated.
+    pub fn new_with_default(key: &[u8], default: fn() -> T) -> Self {
+        Self {
+            cell: TypedCell::new(key),
+            cache: Default::default(),
+            default: Some(default),
+        }
+    }
+
+    /// Returns whether the cell is regarded as holding a default value when
+    /// nothing is stored in it.
+    pub fn has_default(&self) -> bool {
+        self.default.is_some()
+    }
+
+    /// Removes the value from storage immediately. Afterwards the cell holds
+    /// the default value if it has one, or nothing otherwise.
+    pub fn remove(&mut self) {
+        self.cell.remove();
+        let mut cache = self.cache.borrow_mut();
+        cache.update(self.default.map(|default| default()));
+        cache.mark_clean();
+    }
 }
 
 impl<T> Flush for CachedCell<T>
@@ -96,7 +144,7 @@ where
 {
     fn flush(&mut self) {
         if self.cache.borrow().is_dirty() {
-            if let Some(val) = self.cache.borrow_mut().get() {
+            if let Some(val) = self.cache.borrow().get_changed() {
                 self.cell.store(val);
             }
             self.cache.borrow_mut().mark_clean();
@@ -113,9 +161,18 @@ where
         unsafe { (*self.cache.as_ptr()).get() }
     }
 
+    /// Takes the value out of the cell, leaving nothing in it.
+    pub fn take(&mut self) -> Option<T> {
+        self.load();
+        self.cache.borrow_mut().take()
+    }
+
     fn load(&self) {
         if !self.cache.borrow().is_synced() {
-            let loaded = self.cell.load();
+            let loaded = self
+                .cell
+                .load()
+                .or_else(|| self.default.map(|default| default()));
             self.cache.borrow_mut().update(loaded);
         }
     }
@@ -155,6 +212,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lang_core::env;
 
     fn dummy_cell<T>() -> CachedCell<T> {
         CachedCell::new(b"var")
@@ -184,4 +242,58 @@ mod tests {
         // Cache has been flushed before
         assert_eq!(dummy_cell::<i32>().get(), Some(&5));
     }
+
+    #[test]
+    fn default_value() {
+        let mut cell = CachedCell::<i32>::new_with_default(b"var", Default::default);
+        assert_eq!(cell.get(), Some(&0));
+        cell.flush();
+        assert_eq!(dummy_cell::<i32>().get(), None);
+
+        cell.mutate_with(|val| *val += 5);
+        cell.flush();
+        assert_eq!(dummy_cell::<i32>().get(), Some(&5));
+    }
+
+    #[test]
+    fn remove_works() {
+        let mut cell = CachedCell::<i32>::new_with_default(b"var", Default::default);
+        cell.set(5);
+        cell.flush();
+        assert_eq!(dummy_cell::<i32>().get(), Some(&5));
+
+        cell.remove();
+        assert_eq!(cell.get(), Some(&0));
+        cell.flush();
+        assert_eq!(dummy_cell::<i32>().get(), None);
+    }
+
+    #[test]
+    fn unchanged_value_is_not_flushed() {
+        let mut cell = dummy_cell::<i32>();
+        cell.set(5);
+        cell.flush();
+
+        let mut cell = dummy_cell::<i32>();
+        let (_, usage) = env::test::metered(|| {
+            cell.mutate_with(|val| *val *= 1);
+            cell.flush();
+        });
+        assert_eq!(usage.storage_writes, 0);
+
+        let (_, usage) = env::test::metered(|| {
+            cell.mutate_with(|val| *val += 1);
+            cell.mutate_with(|val| *val -= 1);
+            cell.flush();
+        });
+        assert_eq!(usage.storage_writes, 0);
+
+        let (_, usage) = env::test::metered(|| {
+            cell.mutate_with(|val| *val += 1);
+            cell.flush();
+            cell.flush();
+        });
+        assert_eq!(usage.storage_writes, 1);
+        assert_eq!(dummy_cell::<i32>().get(), Some(&6));
+    }
 }
@@ -32,6 +32,12 @@ impl<T> TypedCell<T> {
     }
 }
 
+impl<T> TypedCell<T> {
+    pub fn remove(&mut self) {
+        env::api::remove_storage(&self.key);
+    }
+}
+
 impl<T> TypedCell<T>
 where
     T: scale::Decode,
@@ -10,7 +10,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::lang_core::storage::{CacheEntry, Flush, TypedChunk};
+use crate::lang_core::storage::{Bind, CacheEntry, Flush, TypedChunk};
 use core::{borrow::Borrow, cell::RefCell};
 use liquid_prelude::{collections::BTreeMap, vec::Vec};
 use scale::{Codec, Decode, Encode};
@@ -42,12 +42,7 @@ impl<T> CachedChunk<T> {
         self.chunk.remove(index);
         self.cache.borrow_mut().remove(index.borrow());
     }
-}
 
-impl<T> CachedChunk<T>
-where
-    T: Decode,
-{
     fn get_cache_entry(&self, index: &[u8]) -> Option<&CacheEntry<T>> {
         unsafe { (*self.cache.as_ptr()).get(index.borrow()) }
     }
@@ -55,7 +50,12 @@ where
     fn get_cache_entry_mut(&self, index: &[u8]) -> Option<&mut CacheEntry<T>> {
         unsafe { (*self.cache.as_ptr()).get_mut(index.borrow()) }
     }
+}
 
+impl<T> CachedChunk<T>
+where
+    T: Decode,
+{
     fn sync_from_storage(&self, index: &[u8]) {
         let loaded = self.chunk.load(index);
         self.insert_cache(index, loaded, false);
@@ -71,17 +71,6 @@ where
         }
     }
 
-    pub fn get_mut(&mut self, index: &[u8]) -> Option<&mut T> {
-        let cache_entry = self.get_cache_entry_mut(index);
-        if let Some(entry) = cache_entry {
-            entry.get_mut()
-        } else {
-            self.sync_from_storage(index);
-            self.get_cache_entry_mut(index)
-                .and_then(|entry| entry.get_mut())
-        }
-    }
-
     pub fn take(&mut self, index: &[u8]) -> Option<T> {
         let cache_entry = self.get_cache_entry_mut(index);
         if let Some(entry) = cache_entry {
@@ -98,6 +87,17 @@ impl<T> CachedChunk<T>
 where
     T: Codec,
 {
+    pub fn get_mut(&mut self, index: &[u8]) -> Option<&mut T> {
+        let cache_entry = self.get_cache_entry_mut(index);
+        if let Some(entry) = cache_entry {
+            entry.get_mut_tracked()
+        } else {
+            self.sync_from_storage(index);
+            self.get_cache_entry_mut(index)
+                .and_then(|entry| entry.get_mut_tracked())
+        }
+    }
+
     pub fn set(&mut self, index: &[u8], new_val: T) {
         let cache_entry = self.get_cache_entry_mut(index);
         if let Some(entry) = cache_entry {
@@ -131,17 +131,61 @@ where
     }
 }
 
+impl<T> CachedChunk<T>
+where
+    T: Bind,
+{
+    /// Returns the container bound to the slot at `index`, which is bound with
+    /// the key of the slot on the first access.
+    pub fn bind(&self, index: &[u8]) -> &T {
+        if self.get_cache_entry(index).is_none() {
+            let bound = T::bind_nested(&self.chunk.get_inner_key(index));
+            self.insert_cache(index, Some(bound), false);
+        }
+        self.get_cache_entry(index)
+            .and_then(|entry| entry.get())
+            .expect(
+                "[liquid_lang::CachedChunk::bind] Error: expected container to be bound",
+            )
+    }
+
+    /// Returns a mutable reference to the container bound to the slot at
+    /// `index`, which is bound with the key of the slot on the first access.
+    pub fn bind_mut(&mut self, index: &[u8]) -> &mut T {
+        self.bind(index);
+        self.get_cache_entry_mut(index)
+            .and_then(|entry| entry.get_mut())
+            .expect(
+                "[liquid_lang::CachedChunk::bind_mut] Error: expected container to be \
+                 bound",
+            )
+    }
+}
+
+impl<T> CachedChunk<T>
+where
+    T: Flush,
+{
+    /// Flushes all the containers bound to the slots.
+    pub fn flush_bound(&mut self) {
+        for entry in self.cache.get_mut().values_mut() {
+            if let Some(bound) = entry.get_mut() {
+                bound.flush();
+            }
+        }
+    }
+}
+
 impl<T> Flush for CachedChunk<T>
 where
     T: Encode,
 {
     fn flush(&mut self) {
-        for (index, entry) in &*self.cache.borrow() {
-            if entry.is_dirty() {
-                if let Some(new_val) = entry.get() {
-                    self.chunk.store(index, new_val);
-                }
+        for (index, entry) in self.cache.get_mut().iter_mut() {
+            if let Some(new_val) = entry.get_changed() {
+                self.chunk.store(index, new_val);
             }
+            entry.mark_clean();
         }
     }
 }
@@ -149,6 +193,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lang_core::env;
 
     fn dummy_chunk() -> CachedChunk<u32> {
         CachedChunk::<u32>::new(b"var")
@@ -178,4 +223,31 @@ mod tests {
         // Cache has been flushed before
         assert_eq!(dummy_chunk().get(b"Alice"), Some(&5));
     }
+
+    #[test]
+    fn unchanged_value_is_not_flushed() {
+        let mut chunk = dummy_chunk();
+        chunk.set(b"Alice", 5);
+        chunk.set(b"Bob", 7);
+        chunk.flush();
+
+        let mut chunk = dummy_chunk();
+        let (_, usage) = env::test::metered(|| {
+            chunk.mutate_with(b"Alice", |val| *val *= 1);
+            chunk.mutate_with(b"Bob", |val| *val += 1);
+            chunk.flush();
+            chunk.flush();
+        });
+        assert_eq!(usage.storage_writes, 1);
+        assert_eq!(dummy_chunk().get(b"Alice"), Some(&5));
+        assert_eq!(dummy_chunk().get(b"Bob"), Some(&8));
+
+        let (_, usage) = env::test::metered(|| {
+            let bob = chunk.get_mut(b"Bob").unwrap();
+            *bob = 9;
+            *bob = 8;
+            chunk.flush();
+        });
+        assert_eq!(usage.storage_writes, 0);
+    }
 }
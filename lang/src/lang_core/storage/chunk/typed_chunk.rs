@@ -43,12 +43,21 @@ impl<T> TypedChunk<T> {
     fn prepare_inner_key<Q: AsRef<[u8]>>(&self, index: Q) {
         self.key_buf.borrow_mut().extend_from_slice(index.as_ref());
     }
+
+    /// Returns the key of the slot at `index`.
+    pub fn get_inner_key<Q: AsRef<[u8]>>(&self, index: Q) -> Vec<u8> {
+        self.prepare_inner_key(index);
+        let ret = self.key_buf.borrow().clone();
+        self.key_buf.borrow_mut().truncate(self.prefix_len);
+        ret
+    }
 }
 
 impl<T> TypedChunk<T> {
     pub fn remove<Q: AsRef<[u8]>>(&mut self, index: Q) {
         self.prepare_inner_key(index);
         env::api::remove_storage(self.key_buf.borrow().as_slice());
+        self.key_buf.borrow_mut().truncate(self.prefix_len);
     }
 }
 
@@ -79,15 +88,6 @@ where
 mod tests {
     use super::*;
 
-    impl<T> TypedChunk<T> {
-        pub(crate) fn get_inner_key<Q: AsRef<[u8]>>(&self, index: Q) -> Vec<u8> {
-            self.prepare_inner_key(index);
-            let ret = self.key_buf.borrow().clone();
-            self.key_buf.borrow_mut().truncate(self.prefix_len);
-            ret
-        }
-    }
-
     fn dummy_chunk() -> TypedChunk<u32> {
         TypedChunk::<u32>::new(b"var")
     }
@@ -0,0 +1,847 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::{
+    env::{self, backend::ContainerKind},
+    storage::{
+        Bind, CachedCell, CachedChunk, Flush,
+        You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+    },
+};
+use cfg_if::cfg_if;
+use core::{
+    borrow::Borrow,
+    ops::{Bound, RangeBounds},
+};
+use liquid_prelude::vec::{self, Vec};
+use scale::{Codec, Decode, Encode, Error, Input};
+
+/// The minimum degree of the B-tree, every node except the root contains at
+/// least `MIN_DEGREE - 1` keys and at most `2 * MIN_DEGREE - 1` keys.
+const MIN_DEGREE: usize = 6;
+const MAX_KEYS: usize = 2 * MIN_DEGREE - 1;
+
+/// The id of the root node of an empty tree.
+const NIL: u32 = 0;
+
+#[cfg_attr(feature = "std", derive(Debug))]
+struct Meta {
+    len: u32,
+    root: u32,
+    next_node: u32,
+}
+
+#[cfg_attr(feature = "std", derive(Debug))]
+struct Node<K> {
+    keys: Vec<K>,
+    /// Ids of the children, which is empty for leaves.
+    children: Vec<u32>,
+}
+
+impl Encode for Meta {
+    fn encode(&self) -> vec::Vec<u8> {
+        let mut encoded = self.len.encode();
+        encoded.extend(self.root.encode());
+        encoded.extend(self.next_node.encode());
+        encoded
+    }
+}
+
+impl Decode for Meta {
+    fn decode<I: Input>(value: &mut I) -> Result<Self, Error> {
+        let len = u32::decode(value)?;
+        let root = u32::decode(value)?;
+        let next_node = u32::decode(value)?;
+        Ok(Self {
+            len,
+            root,
+            next_node,
+        })
+    }
+}
+
+impl Default for Meta {
+    fn default() -> Self {
+        Self {
+            len: 0,
+            root: NIL,
+            next_node: NIL + 1,
+        }
+    }
+}
+
+impl<K> Encode for Node<K>
+where
+    K: Encode,
+{
+    fn encode(&self) -> vec::Vec<u8> {
+        let mut encoded = self.keys.encode();
+        encoded.extend(self.children.encode());
+        encoded
+    }
+}
+
+impl<K> Decode for Node<K>
+where
+    K: Decode,
+{
+    fn decode<I: Input>(value: &mut I) -> Result<Self, Error> {
+        let keys = Vec::<K>::decode(value)?;
+        let children = Vec::<u32>::decode(value)?;
+        Ok(Self { keys, children })
+    }
+}
+
+impl<K> Node<K> {
+    fn leaf(keys: Vec<K>) -> Self {
+        Self {
+            keys,
+            children: Vec::new(),
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+fn clone_key<K: Codec>(key: &K) -> K {
+    <K as Decode>::decode(&mut key.encode().as_slice()).unwrap()
+}
+
+/// A mapping which keeps its keys sorted by their `Ord` order.
+///
+/// Keys are organized in a B-tree whose nodes are stored separately, and
+/// values are stored in the same way as `storage::Mapping`, so that `get`,
+/// `insert` of an existing key and `contains_key` don't touch the tree, while
+/// lookups by order only load the nodes on the path they walk through.
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct BTreeMapping<K, V> {
+    meta: CachedCell<Meta>,
+    nodes: CachedChunk<Node<K>>,
+    values: CachedChunk<V>,
+}
+
+/// An iterator over a sub-range of entries of a `BTreeMapping` in ascending
+/// order of keys.
+///
+/// Nodes of the tree are loaded lazily while iterating, so taking the first
+/// `n` entries of a range only loads the nodes containing them.
+pub struct Range<'a, K, V> {
+    mapping: &'a BTreeMapping<K, V>,
+    /// The nodes on the path to the next entry, each with the index of the
+    /// next key to be yielded in it.
+    stack: Vec<(&'a Node<K>, usize)>,
+    end: Bound<K>,
+}
+
+impl<'a, K, V> Range<'a, K, V>
+where
+    K: Codec + Ord,
+    V: Codec,
+{
+    fn new<R>(mapping: &'a BTreeMapping<K, V>, range: R) -> Self
+    where
+        R: RangeBounds<K>,
+    {
+        let end = match range.end_bound() {
+            Bound::Included(key) => Bound::Included(clone_key(key)),
+            Bound::Excluded(key) => Bound::Excluded(clone_key(key)),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let mut iter = Self {
+            mapping,
+            stack: Vec::new(),
+            end,
+        };
+
+        let mut id = mapping.root();
+        while id != NIL {
+            let node = mapping.node(id);
+            let pos = node.keys.partition_point(|key| match range.start_bound() {
+                Bound::Included(start) => key < start,
+                Bound::Excluded(start) => key <= start,
+                Bound::Unbounded => false,
+            });
+            iter.stack.push((node, pos));
+            id = node.children.get(pos).copied().unwrap_or(NIL);
+        }
+        iter
+    }
+
+    fn descend_leftmost(&mut self, mut id: u32) {
+        loop {
+            let node = self.mapping.node(id);
+            self.stack.push((node, 0));
+            match node.children.first() {
+                Some(child) => id = *child,
+                None => return,
+            }
+        }
+    }
+
+    fn is_beyond_end(&self, key: &K) -> bool {
+        match &self.end {
+            Bound::Included(end) => key > end,
+            Bound::Excluded(end) => key >= end,
+            Bound::Unbounded => false,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V>
+where
+    K: Codec + Ord,
+    V: Codec,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, pos) = self.stack.last_mut()?;
+            let node: &'a Node<K> = *node;
+            if *pos == node.keys.len() {
+                self.stack.pop();
+                continue;
+            }
+
+            let key = &node.keys[*pos];
+            *pos += 1;
+            let pos = *pos;
+            if self.is_beyond_end(key) {
+                self.stack.clear();
+                return None;
+            }
+
+            if !node.is_leaf() {
+                self.descend_leftmost(node.children[pos]);
+            }
+            let val = self.mapping.values.get(&key.encode()).expect(
+                "[liquid_lang::BTreeMapping::range] Error: expected value of key in the \
+                 tree to be existed",
+            );
+            return Some((key, val));
+        }
+    }
+}
+
+impl<K, V> Bind for BTreeMapping<K, V> {
+    fn bind_with(key: &[u8]) -> Self {
+        let mut nodes_bind_key = key.to_vec();
+        nodes_bind_key.extend_from_slice(b"$nodes");
+        let mut values_bind_key = key.to_vec();
+        values_bind_key.extend_from_slice(b"$values");
+
+        env::api::bind_container(key, ContainerKind::Value);
+        env::api::bind_container(&nodes_bind_key, ContainerKind::Vec);
+        env::api::bind_container(&values_bind_key, ContainerKind::Mapping);
+
+        Self {
+            meta: CachedCell::new(key),
+            nodes: CachedChunk::new(&nodes_bind_key),
+            values: CachedChunk::new(&values_bind_key),
+        }
+    }
+
+    fn bind_nested(key: &[u8]) -> Self {
+        let mut btree_mapping = Self::bind_with(key);
+        btree_mapping.meta = CachedCell::new_with_default(key, Meta::default);
+        btree_mapping
+    }
+}
+
+impl<K, V> Flush for BTreeMapping<K, V>
+where
+    K: Encode,
+    V: Encode,
+{
+    fn flush(&mut self) {
+        self.meta.flush();
+        self.nodes.flush();
+        self.values.flush();
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "contract")] {
+        use crate::lang_core::storage::Getter;
+
+        impl<K, V> Getter for BTreeMapping<K, V>
+        where
+            K: Codec + Ord,
+            V: Codec + Clone,
+        {
+            type Index = K;
+            type Output = V;
+
+            fn getter_impl(&self, index: Self::Index) -> Self::Output {
+                self.get(&index)
+                    .expect(
+                        "[liquid_lang::BTreeMapping::getter] Error: expected `index` to be \
+                         existed",
+                    )
+                    .clone()
+            }
+        }
+    }
+}
+
+impl<K, V> BTreeMapping<K, V>
+where
+    K: Codec + Ord,
+    V: Codec,
+{
+    pub fn initialize(&mut self) {
+        if self.meta.get().is_none() {
+            self.meta.set(Meta::default());
+        }
+    }
+
+    fn meta(&self) -> &Meta {
+        self.meta.get().expect(
+            "[liquid_lang::BTreeMapping] Error: expected `meta` field to be existed in \
+             storage",
+        )
+    }
+
+    fn meta_mut(&mut self) -> &mut Meta {
+        self.meta.get_mut().expect(
+            "[liquid_lang::BTreeMapping] Error: expected `meta` field to be existed in \
+             storage",
+        )
+    }
+
+    pub fn len(&self) -> u32 {
+        self.meta().len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn root(&self) -> u32 {
+        self.meta().root
+    }
+
+    fn node(&self, id: u32) -> &Node<K> {
+        self.nodes.get(&id.to_le_bytes()).expect(
+            "[liquid_lang::BTreeMapping] Error: expected tree node to be existed in \
+             storage",
+        )
+    }
+
+    fn take_node(&mut self, id: u32) -> Node<K> {
+        self.nodes.take(&id.to_le_bytes()).expect(
+            "[liquid_lang::BTreeMapping] Error: expected tree node to be existed in \
+             storage",
+        )
+    }
+
+    fn put_node(&mut self, id: u32, node: Node<K>) {
+        self.nodes.set(&id.to_le_bytes(), node);
+    }
+
+    fn alloc_node(&mut self, node: Node<K>) -> u32 {
+        let meta = self.meta_mut();
+        let id = meta.next_node;
+        meta.next_node = id.checked_add(1).expect(
+            "[liquid_lang::BTreeMapping] Error: cannot allocate more than `u32::MAX` \
+             tree nodes",
+        );
+        self.put_node(id, node);
+        id
+    }
+
+    fn free_node(&mut self, id: u32) {
+        self.nodes.remove(&id.to_le_bytes());
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, `None` is returned.
+    ///
+    /// If the map did have this key present, the value is updated,
+    /// and the old value is returned.
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        let encoded_key = key.encode();
+        if self.values.get(&encoded_key).is_some() {
+            return self.values.put(&encoded_key, val);
+        }
+
+        if self.len() == u32::MAX {
+            panic!(
+                "[liquid_lang::BTreeMapping::insert] Error: cannot insert more elements \
+                 than `u32::MAX`"
+            );
+        }
+
+        self.values.set(&encoded_key, val);
+        self.insert_key(key);
+        self.meta_mut().len += 1;
+        None
+    }
+
+    fn insert_key(&mut self, key: K) {
+        let root = self.root();
+        if root == NIL {
+            let mut keys = Vec::new();
+            keys.push(key);
+            let root = self.alloc_node(Node::leaf(keys));
+            self.meta_mut().root = root;
+            return;
+        }
+
+        let mut id = root;
+        if self.node(root).keys.len() == MAX_KEYS {
+            let mut children = Vec::new();
+            children.push(root);
+            id = self.alloc_node(Node {
+                keys: Vec::new(),
+                children,
+            });
+            self.meta_mut().root = id;
+            self.split_child(id, 0);
+        }
+
+        // Every node on the path is guaranteed to be not full before descending
+        // into it, so that the insertion never needs to walk back.
+        loop {
+            let (pos, child) = {
+                let node = self.node(id);
+                let pos = node.keys.partition_point(|k| k < &key);
+                (pos, node.children.get(pos).copied())
+            };
+
+            let mut child = match child {
+                Some(child) => child,
+                None => {
+                    let mut node = self.take_node(id);
+                    node.keys.insert(pos, key);
+                    self.put_node(id, node);
+                    return;
+                }
+            };
+
+            if self.node(child).keys.len() == MAX_KEYS {
+                self.split_child(id, pos);
+                let node = self.node(id);
+                if node.keys[pos] < key {
+                    child = node.children[pos + 1];
+                }
+            }
+            id = child;
+        }
+    }
+
+    /// Splits the full `i`-th child of `parent`, the median key of the child
+    /// is moved up into `parent`.
+    fn split_child(&mut self, parent: u32, i: usize) {
+        let mut parent_node = self.take_node(parent);
+        let child = parent_node.children[i];
+        let mut child_node = self.take_node(child);
+
+        let right_keys = child_node.keys.split_off(MIN_DEGREE);
+        let median = child_node.keys.pop().expect(
+            "[liquid_lang::BTreeMapping] Error: expected full node to have a median key",
+        );
+        let right_children = if child_node.is_leaf() {
+            Vec::new()
+        } else {
+            child_node.children.split_off(MIN_DEGREE)
+        };
+
+        let right = self.alloc_node(Node {
+            keys: right_keys,
+            children: right_children,
+        });
+        parent_node.keys.insert(i, median);
+        parent_node.children.insert(i + 1, right);
+        self.put_node(child, child_node);
+        self.put_node(parent, parent_node);
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Encode + Ord + ?Sized,
+    {
+        let encoded_key = key.encode();
+        let ret = self.values.take(&encoded_key);
+        self.values.remove(&encoded_key);
+
+        if ret.is_some() {
+            self.remove_key(key);
+            self.meta_mut().len -= 1;
+        }
+        ret
+    }
+
+    /// Removes `key` from the tree.
+    ///
+    /// Every node on the path except the root is guaranteed to contain at least
+    /// `MIN_DEGREE` keys before descending into it, so that removing a key from
+    /// a leaf never makes the leaf underflow.
+    fn remove_key<Q>(&mut self, key: &Q) -> K
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut id = self.root();
+        loop {
+            let (found, pos, is_leaf) = {
+                let node = self.node(id);
+                match node.keys.binary_search_by(|k| k.borrow().cmp(key)) {
+                    Ok(pos) => (true, pos, node.is_leaf()),
+                    Err(pos) => (false, pos, node.is_leaf()),
+                }
+            };
+
+            if is_leaf {
+                assert!(
+                    found,
+                    "[liquid_lang::BTreeMapping::remove] Error: expected key to be \
+                     existed in the tree"
+                );
+                let mut node = self.take_node(id);
+                let removed = node.keys.remove(pos);
+                self.put_leaf(id, node);
+                return removed;
+            }
+
+            if !found {
+                id = self.ensure_child(id, pos);
+                continue;
+            }
+
+            let (left, right) = {
+                let node = self.node(id);
+                (node.children[pos], node.children[pos + 1])
+            };
+            let replacement = if self.node(left).keys.len() >= MIN_DEGREE {
+                self.remove_extreme(left, true)
+            } else if self.node(right).keys.len() >= MIN_DEGREE {
+                self.remove_extreme(right, false)
+            } else {
+                id = self.merge_children(id, pos);
+                continue;
+            };
+
+            let mut node = self.take_node(id);
+            let removed = core::mem::replace(&mut node.keys[pos], replacement);
+            self.put_node(id, node);
+            return removed;
+        }
+    }
+
+    /// Removes the maximum key if `max` is `true`, or the minimum key otherwise,
+    /// from the subtree rooted at `id`.
+    fn remove_extreme(&mut self, mut id: u32, max: bool) -> K {
+        loop {
+            let (len, children_len) = {
+                let node = self.node(id);
+                (node.keys.len(), node.children.len())
+            };
+
+            if children_len == 0 {
+                let mut node = self.take_node(id);
+                let removed = if max {
+                    node.keys.pop().expect(
+                        "[liquid_lang::BTreeMapping] Error: expected leaf to be not \
+                         empty",
+                    )
+                } else {
+                    node.keys.remove(0)
+                };
+                self.put_leaf(id, node);
+                return removed;
+            }
+
+            id = self.ensure_child(id, if max { len } else { 0 });
+        }
+    }
+
+    /// Writes back a leaf which has just lost a key, and frees it if it's an
+    /// empty root.
+    fn put_leaf(&mut self, id: u32, node: Node<K>) {
+        if node.keys.is_empty() && id == self.root() {
+            self.free_node(id);
+            self.meta_mut().root = NIL;
+        } else {
+            self.put_node(id, node);
+        }
+    }
+
+    /// Makes sure the `i`-th child of `parent` contains at least `MIN_DEGREE`
+    /// keys, by borrowing a key from one of its siblings or merging it with
+    /// one of them. Returns the id of the node which covers the keys of the
+    /// child afterwards.
+    fn ensure_child(&mut self, parent: u32, i: usize) -> u32 {
+        let (child, left, right) = {
+            let node = self.node(parent);
+            (
+                node.children[i],
+                i.checked_sub(1).map(|j| node.children[j]),
+                node.children.get(i + 1).copied(),
+            )
+        };
+        if self.node(child).keys.len() >= MIN_DEGREE {
+            return child;
+        }
+
+        if let Some(left) = left {
+            if self.node(left).keys.len() >= MIN_DEGREE {
+                let mut parent_node = self.take_node(parent);
+                let mut left_node = self.take_node(left);
+                let mut child_node = self.take_node(child);
+
+                let borrowed = left_node.keys.pop().expect(
+                    "[liquid_lang::BTreeMapping] Error: expected sibling to be not empty",
+                );
+                let separator =
+                    core::mem::replace(&mut parent_node.keys[i - 1], borrowed);
+                child_node.keys.insert(0, separator);
+                if let Some(grandchild) = left_node.children.pop() {
+                    child_node.children.insert(0, grandchild);
+                }
+
+                self.put_node(parent, parent_node);
+                self.put_node(left, left_node);
+                self.put_node(child, child_node);
+                return child;
+            }
+        }
+
+        if let Some(right) = right {
+            if self.node(right).keys.len() >= MIN_DEGREE {
+                let mut parent_node = self.take_node(parent);
+                let mut right_node = self.take_node(right);
+                let mut child_node = self.take_node(child);
+
+                let borrowed = right_node.keys.remove(0);
+                let separator = core::mem::replace(&mut parent_node.keys[i], borrowed);
+                child_node.keys.push(separator);
+                if !right_node.is_leaf() {
+                    child_node.children.push(right_node.children.remove(0));
+                }
+
+                self.put_node(parent, parent_node);
+                self.put_node(right, right_node);
+                self.put_node(child, child_node);
+                return child;
+            }
+        }
+
+        if right.is_some() {
+            self.merge_children(parent, i)
+        } else {
+            self.merge_children(parent, i - 1)
+        }
+    }
+
+    /// Merges the `i + 1`-th child of `parent` and the separator key between
+    /// them into the `i`-th child, and returns the id of the merged node. The
+    /// merged node becomes the new root if `parent` is the root and becomes
+    /// empty.
+    fn merge_children(&mut self, parent: u32, i: usize) -> u32 {
+        let mut parent_node = self.take_node(parent);
+        let left = parent_node.children[i];
+        let right = parent_node.children.remove(i + 1);
+        let separator = parent_node.keys.remove(i);
+
+        let mut left_node = self.take_node(left);
+        let right_node = self.take_node(right);
+        left_node.keys.push(separator);
+        left_node.keys.extend(right_node.keys);
+        left_node.children.extend(right_node.children);
+        self.put_node(left, left_node);
+        self.free_node(right);
+
+        if parent_node.keys.is_empty() && parent == self.root() {
+            self.free_node(parent);
+            self.meta_mut().root = left;
+        } else {
+            self.put_node(parent, parent_node);
+        }
+        left
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Encode + ?Sized,
+    {
+        self.values.get(&key.encode()).is_some()
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Encode + ?Sized,
+    {
+        self.values.get(&key.encode())
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Encode + ?Sized,
+    {
+        self.values.get_mut(&key.encode())
+    }
+
+    pub fn mutate_with<Q, F>(&mut self, key: &Q, f: F) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Encode + ?Sized,
+        F: FnOnce(&mut V),
+    {
+        self.values.mutate_with(&key.encode(), f)
+    }
+
+    /// Returns an iterator over all entries in ascending order of keys.
+    pub fn iter(&self) -> Range<'_, K, V> {
+        Range::new(self, ..)
+    }
+
+    /// Returns an iterator over the entries whose keys are in `range`, in
+    /// ascending order of keys.
+    pub fn range<R>(&self, range: R) -> Range<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        Range::new(self, range)
+    }
+
+    /// Returns at most `limit` entries following the key `after`, or from the
+    /// first entry if `after` is `None`.
+    ///
+    /// The last key returned can be passed as `after` to fetch the next page.
+    pub fn page(
+        &self,
+        after: Option<&K>,
+        limit: usize,
+    ) -> core::iter::Take<Range<'_, K, V>> {
+        let start = match after {
+            Some(after) => Bound::Excluded(after),
+            None => Bound::Unbounded,
+        };
+        Range::new(self, (start, Bound::Unbounded)).take(limit)
+    }
+
+    /// Returns the entry with the minimum key.
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.extreme_key(false).map(|key| (key, &self[key]))
+    }
+
+    /// Returns the entry with the maximum key.
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.extreme_key(true).map(|key| (key, &self[key]))
+    }
+
+    fn extreme_key(&self, max: bool) -> Option<&K> {
+        let mut id = self.root();
+        if id == NIL {
+            return None;
+        }
+
+        loop {
+            let node = self.node(id);
+            let child = if max {
+                node.children.last()
+            } else {
+                node.children.first()
+            };
+            match child {
+                Some(child) => id = *child,
+                None if max => return node.keys.last(),
+                None => return node.keys.first(),
+            }
+        }
+    }
+
+    /// Removes and returns the entry with the minimum key.
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        self.pop_extreme(false)
+    }
+
+    /// Removes and returns the entry with the maximum key.
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        self.pop_extreme(true)
+    }
+
+    fn pop_extreme(&mut self, max: bool) -> Option<(K, V)> {
+        let root = self.root();
+        if root == NIL {
+            return None;
+        }
+
+        let key = self.remove_extreme(root, max);
+        let encoded_key = key.encode();
+        let val = self.values.take(&encoded_key).expect(
+            "[liquid_lang::BTreeMapping] Error: expected value of key in the tree to be \
+             existed",
+        );
+        self.values.remove(&encoded_key);
+        self.meta_mut().len -= 1;
+        Some((key, val))
+    }
+}
+
+impl<'a, K, V, Q> core::ops::Index<&'a Q> for BTreeMapping<K, V>
+where
+    K: Borrow<Q> + Codec + Ord,
+    V: Codec,
+    Q: Encode + ?Sized,
+{
+    type Output = V;
+
+    fn index(&self, index: &'a Q) -> &Self::Output {
+        self.get(index).expect(
+            "[liquid_lang::BTreeMapping::index] Error: expected `index` to be existed",
+        )
+    }
+}
+
+impl<'a, K, V, Q> core::ops::IndexMut<&'a Q> for BTreeMapping<K, V>
+where
+    K: Borrow<Q> + Codec + Ord,
+    V: Codec,
+    Q: Encode + ?Sized,
+{
+    fn index_mut(&mut self, index: &'a Q) -> &mut Self::Output {
+        self.get_mut(index).expect(
+            "[liquid_lang::BTreeMapping::index_mut] Error: expected `index` to be \
+             existed",
+        )
+    }
+}
+
+impl<K, V> Extend<(K, V)> for BTreeMapping<K, V>
+where
+    K: Codec + Ord,
+    V: Codec,
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<K, V> You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage
+    for BTreeMapping<K, V>
+{
+    type Wrapped1 = K;
+    type Wrapped2 = V;
+}
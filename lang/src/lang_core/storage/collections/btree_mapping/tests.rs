@@ -0,0 +1,206 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::BTreeMapping;
+use crate::lang_core::{
+    env::test,
+    storage::traits::{Bind, Flush},
+};
+use core::ops::Bound;
+use scale::Codec;
+use std::collections::BTreeMap;
+
+fn new_empty<K: Codec + Ord, V: Codec>() -> BTreeMapping<K, V> {
+    let mut map = BTreeMapping::<K, V>::bind_with(b"var");
+    map.initialize();
+    map
+}
+
+/// Returns `0..n` in a scrambled but deterministic order.
+fn scrambled(n: u32) -> Vec<u32> {
+    (0..n).map(|i| (i * 7919) % n).collect()
+}
+
+fn assert_same(map: &BTreeMapping<u32, u32>, expected: &BTreeMap<u32, u32>) {
+    assert_eq!(map.len() as usize, expected.len());
+    assert!(map.iter().eq(expected.iter()));
+    assert_eq!(map.first(), expected.iter().next());
+    assert_eq!(map.last(), expected.iter().next_back());
+}
+
+#[test]
+fn empty() {
+    let map = new_empty::<String, u8>();
+    assert_eq!(map.len(), 0);
+    assert_eq!(map.is_empty(), true);
+    assert_eq!(map.first(), None);
+    assert_eq!(map.last(), None);
+    assert_eq!(map.iter().next(), None);
+}
+
+#[test]
+fn insert_works() {
+    let mut map = new_empty::<String, u8>();
+    let name = "Alice".to_string();
+    assert_eq!(map.insert(name.clone(), 0), None);
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.insert(name.clone(), 1), Some(0));
+    assert_eq!(map.len(), 1);
+    assert_eq!(map[&name], 1);
+    assert_eq!(map.contains_key(&name), true);
+    assert_eq!(map.contains_key(&"Bob".to_string()), false);
+}
+
+#[test]
+fn iter_is_ordered() {
+    let mut map = new_empty::<u32, u32>();
+    let mut expected = BTreeMap::new();
+    for i in scrambled(1000) {
+        map.insert(i, i * 2);
+        expected.insert(i, i * 2);
+    }
+    assert_same(&map, &expected);
+}
+
+#[test]
+fn range_works() {
+    let mut map = new_empty::<u32, u32>();
+    for i in scrambled(500) {
+        map.insert(i * 2, i);
+    }
+
+    let keys =
+        |iter: super::impls::Range<u32, u32>| iter.map(|(k, _)| *k).collect::<Vec<_>>();
+    assert_eq!(keys(map.range(10..16)), vec![10, 12, 14]);
+    assert_eq!(keys(map.range(11..=16)), vec![12, 14, 16]);
+    assert_eq!(keys(map.range(995..)), vec![996, 998]);
+    assert_eq!(keys(map.range(..5)), vec![0, 2, 4]);
+    assert_eq!(
+        keys(map.range((Bound::Excluded(10), Bound::Included(14)))),
+        vec![12, 14]
+    );
+    assert_eq!(keys(map.range(1000..)), vec![]);
+    assert_eq!(keys(map.range(3..4)), vec![]);
+}
+
+#[test]
+fn page_works() {
+    let mut map = new_empty::<u32, u32>();
+    for i in scrambled(95) {
+        map.insert(i, i);
+    }
+
+    let mut after = None;
+    let mut pages = 0;
+    let mut visited = Vec::new();
+    loop {
+        let page = map
+            .page(after.as_ref(), 20)
+            .map(|(k, _)| *k)
+            .collect::<Vec<_>>();
+        if page.is_empty() {
+            break;
+        }
+        after = page.last().copied();
+        visited.extend(page);
+        pages += 1;
+    }
+    assert_eq!(pages, 5);
+    assert_eq!(visited, (0..95).collect::<Vec<_>>());
+}
+
+#[test]
+fn remove_works() {
+    let mut map = new_empty::<u32, u32>();
+    let mut expected = BTreeMap::new();
+    for i in scrambled(600) {
+        map.insert(i, i);
+        expected.insert(i, i);
+    }
+
+    for (n, i) in scrambled(600).into_iter().enumerate() {
+        if n % 3 == 0 {
+            continue;
+        }
+        assert_eq!(map.remove(&i), expected.remove(&i));
+        assert_eq!(map.remove(&i), None);
+        if n % 50 == 0 {
+            assert_same(&map, &expected);
+        }
+    }
+    assert_same(&map, &expected);
+
+    for i in 0..600 {
+        assert_eq!(map.remove(&i), expected.remove(&i));
+    }
+    assert_same(&map, &expected);
+    assert!(map.is_empty());
+
+    map.insert(42, 42);
+    assert_eq!(map.first(), Some((&42, &42)));
+}
+
+#[test]
+fn pop_works() {
+    let mut map = new_empty::<u32, u32>();
+    for i in scrambled(200) {
+        map.insert(i, i + 1);
+    }
+
+    for i in 0..100 {
+        assert_eq!(map.pop_first(), Some((i, i + 1)));
+        assert_eq!(map.pop_last(), Some((199 - i, 200 - i)));
+    }
+    assert_eq!(map.pop_first(), None);
+    assert_eq!(map.pop_last(), None);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn multi_session() {
+    let mut map = new_empty::<u32, u32>();
+    let mut expected = BTreeMap::new();
+    for i in scrambled(300) {
+        map.insert(i, i);
+        expected.insert(i, i);
+    }
+    map.flush();
+
+    let mut map = BTreeMapping::<u32, u32>::bind_with(b"var");
+    assert_same(&map, &expected);
+    for i in 100..200 {
+        assert_eq!(map.remove(&i), expected.remove(&i));
+    }
+    map.flush();
+
+    let map = BTreeMapping::<u32, u32>::bind_with(b"var");
+    assert_same(&map, &expected);
+}
+
+#[test]
+fn lazy_loading() {
+    let mut map = new_empty::<u32, u32>();
+    for i in scrambled(1000) {
+        map.insert(i, i);
+    }
+    map.flush();
+
+    let map = BTreeMapping::<u32, u32>::bind_with(b"var");
+    let (page, usage) = test::metered(|| {
+        map.page(Some(&500), 20)
+            .map(|(k, v)| (*k, *v))
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(page, (501..521).map(|i| (i, i)).collect::<Vec<_>>());
+    // The meta, the nodes on the paths and the values on the page.
+    assert!(usage.storage_reads < 50);
+}
@@ -0,0 +1,383 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::{
+    env::{self, backend::ContainerKind},
+    storage::{
+        Bind, CachedCell, CachedChunk, Flush,
+        You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+    },
+};
+use cfg_if::cfg_if;
+use liquid_prelude::vec;
+use scale::{Codec, Decode, Encode, Error, Input};
+
+/// The position of the elements in the ring of slots.
+#[derive(Default)]
+#[cfg_attr(feature = "std", derive(Debug))]
+struct Bounds {
+    /// The slot of the front element.
+    head: u32,
+    len: u32,
+}
+
+impl Encode for Bounds {
+    fn encode(&self) -> vec::Vec<u8> {
+        let mut encoded = self.head.encode();
+        encoded.extend(self.len.encode());
+        encoded
+    }
+}
+
+impl Decode for Bounds {
+    fn decode<I: Input>(value: &mut I) -> Result<Self, Error> {
+        let head = u32::decode(value)?;
+        let len = u32::decode(value)?;
+        Ok(Self { head, len })
+    }
+}
+
+/// A double-ended queue stored in the contract storage.
+///
+/// The elements are stored in a ring of `u32::MAX + 1` slots, the slot of
+/// the front element and the number of elements are stored at the bound key,
+/// and the `n`-th slot is stored at the bound key followed by `$` and `n` in
+/// little endian. Pushing and popping at both ends are O(1).
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Deque<T> {
+    bounds: CachedCell<Bounds>,
+    chunk: CachedChunk<T>,
+}
+
+pub struct Iter<'a, T> {
+    deque: &'a Deque<T>,
+    begin: u32,
+    end: u32,
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub(crate) fn new(deque: &'a Deque<T>) -> Self {
+        Self {
+            deque,
+            begin: 0,
+            end: deque.len(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: Codec,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        debug_assert!(self.begin <= self.end);
+
+        if self.begin == self.end {
+            return None;
+        }
+
+        let ret = self.deque.get(self.begin);
+        self.begin += 1;
+        ret
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end - self.begin) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> where T: Codec {}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T>
+where
+    T: Codec,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        debug_assert!(self.begin <= self.end);
+
+        if self.begin == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        self.deque.get(self.end)
+    }
+}
+
+impl<T> Bind for Deque<T> {
+    fn bind_with(key: &[u8]) -> Self {
+        env::api::bind_container(key, ContainerKind::Deque);
+
+        Self {
+            bounds: CachedCell::<Bounds>::new(key),
+            chunk: CachedChunk::<T>::new(key),
+        }
+    }
+
+    fn bind_nested(key: &[u8]) -> Self {
+        let mut deque = Self::bind_with(key);
+        deque.bounds = CachedCell::<Bounds>::new_with_default(key, Bounds::default);
+        deque
+    }
+}
+
+impl<T> Flush for Deque<T>
+where
+    T: Encode,
+{
+    fn flush(&mut self) {
+        self.bounds.flush();
+        self.chunk.flush();
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "contract")] {
+        use crate::lang_core::storage::Getter;
+
+        impl<T> Getter for Deque<T>
+        where
+            T: Codec + Clone,
+        {
+            type Index = u32;
+            type Output = T;
+
+            fn getter_impl(&self, index: Self::Index) -> Self::Output {
+                self.get(index)
+                    .expect(
+                        "[liquid_lang::Deque::getter] Error: expected `index` to be within \
+                         bounds",
+                    )
+                    .clone()
+            }
+        }
+    }
+}
+
+impl<T> Deque<T> {
+    pub fn initialize(&mut self) {
+        if self.bounds.get().is_none() {
+            self.bounds.set(Bounds::default());
+        }
+    }
+
+    fn bounds(&self) -> &Bounds {
+        self.bounds.get().expect(
+            "[liquid_lang::Deque::bounds] Error: expected `bounds` field to be existed \
+             in storage",
+        )
+    }
+
+    fn bounds_mut(&mut self) -> &mut Bounds {
+        self.bounds.get_mut().expect(
+            "[liquid_lang::Deque::bounds_mut] Error: expected `bounds` field to be \
+             existed in storage",
+        )
+    }
+
+    pub fn len(&self) -> u32 {
+        self.bounds().len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::<T>::new(self)
+    }
+
+    /// Returns the slot of the `n`-th element if `n` is within bounds.
+    fn slot(&self, n: u32) -> Option<[u8; 4]> {
+        let bounds = self.bounds();
+        if n < bounds.len {
+            return Some(bounds.head.wrapping_add(n).to_le_bytes());
+        }
+        None
+    }
+}
+
+impl<T> Deque<T>
+where
+    T: Codec,
+{
+    /// Returns a reference to the `n`-th element from the front of the queue.
+    ///
+    /// Returns `None` if `n` is out of bounds.
+    pub fn get(&self, n: u32) -> Option<&T> {
+        self.slot(n).and_then(|slot| self.chunk.get(&slot))
+    }
+
+    /// Returns a mutable reference to the `n`-th element from the front of the
+    /// queue.
+    ///
+    /// Returns `None` if `n` is out of bounds.
+    pub fn get_mut(&mut self, n: u32) -> Option<&mut T> {
+        self.slot(n).and_then(move |slot| self.chunk.get_mut(&slot))
+    }
+
+    /// Mutates the `n`-th element from the front of the queue.
+    ///
+    /// Returns a reference to the mutated element.
+    /// Returns `None` and won't mutate if `n` out of bounds.
+    pub fn mutate_with<F>(&mut self, n: u32, f: F) -> Option<&T>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.slot(n)
+            .and_then(move |slot| self.chunk.mutate_with(&slot, f))
+    }
+
+    /// Returns a reference to the front element, or `None` if the queue is
+    /// empty.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a mutable reference to the front element, or `None` if the
+    /// queue is empty.
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Returns a reference to the back element, or `None` if the queue is
+    /// empty.
+    pub fn back(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|n| self.get(n))
+    }
+
+    /// Returns a mutable reference to the back element, or `None` if the
+    /// queue is empty.
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.len().checked_sub(1).and_then(move |n| self.get_mut(n))
+    }
+
+    /// Appends an element to the back of the queue.
+    pub fn push_back(&mut self, val: T) {
+        if self.len() == u32::MAX {
+            panic!(
+                "[liquid_lang::Deque::push_back] Error: cannot push more elements than \
+                 `u32::MAX`"
+            );
+        }
+
+        let bounds = self.bounds_mut();
+        let slot = bounds.head.wrapping_add(bounds.len);
+        bounds.len += 1;
+        self.chunk.set(&slot.to_le_bytes(), val);
+    }
+
+    /// Prepends an element to the front of the queue.
+    pub fn push_front(&mut self, val: T) {
+        if self.len() == u32::MAX {
+            panic!(
+                "[liquid_lang::Deque::push_front] Error: cannot push more elements than \
+                 `u32::MAX`"
+            );
+        }
+
+        let bounds = self.bounds_mut();
+        bounds.head = bounds.head.wrapping_sub(1);
+        bounds.len += 1;
+        let slot = bounds.head;
+        self.chunk.set(&slot.to_le_bytes(), val);
+    }
+
+    /// Removes the front element of the queue and returns it, or `None` if
+    /// the queue is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let bounds = self.bounds_mut();
+        let slot = bounds.head.to_le_bytes();
+        bounds.head = bounds.head.wrapping_add(1);
+        bounds.len -= 1;
+        let ret = self.chunk.take(&slot);
+        self.chunk.remove(&slot);
+        ret
+    }
+
+    /// Removes the back element of the queue and returns it, or `None` if
+    /// the queue is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let bounds = self.bounds_mut();
+        bounds.len -= 1;
+        let slot = bounds.head.wrapping_add(bounds.len).to_le_bytes();
+        let ret = self.chunk.take(&slot);
+        self.chunk.remove(&slot);
+        ret
+    }
+}
+
+impl<T> Extend<T> for Deque<T>
+where
+    T: Codec,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for i in iter {
+            self.push_back(i);
+        }
+    }
+}
+
+impl<'a, T> Extend<&'a T> for Deque<T>
+where
+    T: Codec + Copy + 'a,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+impl<T> core::ops::Index<u32> for Deque<T>
+where
+    T: Codec,
+{
+    type Output = T;
+
+    fn index(&self, index: u32) -> &Self::Output {
+        self.get(index).expect(
+            "[liquid_lang::Deque::index] Error: expected `index` to be within bounds",
+        )
+    }
+}
+
+impl<T> core::ops::IndexMut<u32> for Deque<T>
+where
+    T: Codec,
+{
+    fn index_mut(&mut self, index: u32) -> &mut Self::Output {
+        self.get_mut(index).expect(
+            "[liquid_lang::Deque::index_mut] Error: expected `index` to be within bounds",
+        )
+    }
+}
+
+impl<T> You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage for Deque<T> {
+    type Wrapped1 = T;
+    type Wrapped2 = ();
+}
@@ -0,0 +1,117 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Deque;
+use crate::lang_core::storage::traits::{Bind, Flush};
+
+fn new_empty_deque() -> Deque<u8> {
+    let mut deque = Deque::<u8>::bind_with(b"deque");
+    deque.initialize();
+    deque
+}
+
+#[test]
+fn empty() {
+    let mut deque = new_empty_deque();
+    assert_eq!(deque.len(), 0);
+    assert!(deque.is_empty());
+    assert_eq!(deque.front(), None);
+    assert_eq!(deque.back(), None);
+    assert_eq!(deque.pop_front(), None);
+    assert_eq!(deque.pop_back(), None);
+    assert_eq!(deque.iter().next(), None);
+}
+
+#[test]
+fn push_and_pop_works() {
+    let mut deque = new_empty_deque();
+    deque.push_back(1);
+    deque.push_back(2);
+    deque.push_front(0);
+    assert_eq!(deque.len(), 3);
+    assert_eq!(deque.front(), Some(&0));
+    assert_eq!(deque.back(), Some(&2));
+
+    assert_eq!(deque.pop_front(), Some(0));
+    assert_eq!(deque.pop_back(), Some(2));
+    assert_eq!(deque.pop_back(), Some(1));
+    assert_eq!(deque.pop_front(), None);
+    assert!(deque.is_empty());
+}
+
+#[test]
+fn fifo_works() {
+    let mut deque = new_empty_deque();
+    for round in 0..10 {
+        deque.extend(&[2 * round, 2 * round + 1]);
+        assert_eq!(deque.pop_front(), Some(round));
+    }
+    assert_eq!(deque.len(), 10);
+    assert_eq!(
+        deque.iter().copied().collect::<Vec<_>>(),
+        (10..20).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn wraps_around() {
+    let mut deque = new_empty_deque();
+    deque.push_front(1);
+    deque.push_front(0);
+    deque.push_back(2);
+    assert_eq!(deque.iter().collect::<Vec<_>>(), vec![&0, &1, &2]);
+    assert_eq!(deque.iter().rev().collect::<Vec<_>>(), vec![&2, &1, &0]);
+    assert_eq!(deque.pop_back(), Some(2));
+    assert_eq!(deque.pop_back(), Some(1));
+    assert_eq!(deque.back(), Some(&0));
+    assert_eq!(deque.pop_back(), Some(0));
+    assert_eq!(deque.pop_back(), None);
+}
+
+#[test]
+fn index_works() {
+    let mut deque = new_empty_deque();
+    deque.extend(&[1, 2, 3]);
+    deque.push_front(0);
+    assert_eq!(deque[0], 0);
+    assert_eq!(deque[3], 3);
+    deque[1] = 10;
+    *deque.back_mut().unwrap() = 30;
+    assert_eq!(deque.mutate_with(2, |x| *x += 18), Some(&20));
+    assert_eq!(deque.get(4), None);
+    assert_eq!(deque.iter().collect::<Vec<_>>(), vec![&0, &10, &20, &30]);
+}
+
+#[test]
+#[should_panic]
+fn index_out_of_bounds() {
+    let mut deque = new_empty_deque();
+    deque.push_front(0);
+    let _ = deque[1];
+}
+
+#[test]
+fn multi_session() {
+    let mut deque = new_empty_deque();
+    deque.extend(&[1, 2]);
+    deque.push_front(0);
+    deque.flush();
+
+    let mut deque = Deque::<u8>::bind_with(b"deque");
+    assert_eq!(deque.len(), 3);
+    assert_eq!(deque.pop_front(), Some(0));
+    deque.push_back(3);
+    deque.flush();
+
+    let deque = Deque::<u8>::bind_with(b"deque");
+    assert_eq!(deque.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+}
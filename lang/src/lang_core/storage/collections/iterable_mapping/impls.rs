@@ -75,6 +75,13 @@ where
     }
 }
 
+/// A mapping stored in the contract storage which can be iterated over.
+///
+/// The keys are recorded in a `storage::Vec` besides a `storage::Mapping`
+/// holding the values. Keys are iterated in the order of insertion as long as
+/// nothing is removed, but removing a key moves the last key into its slot,
+/// hence the iteration order is not preserved after removal, e.g., removing
+/// `a` from a mapping whose keys are `[a, b, c]` leaves `[c, b]`.
 #[cfg_attr(feature = "std", derive(Debug))]
 pub struct IterableMapping<K, V> {
     keys: Vec<KeyEntry<K>>,
@@ -138,16 +145,33 @@ where
     V: Codec,
 {
     fn bind_with(key: &[u8]) -> Self {
-        let mut keys_bind_key = key.to_vec();
-        keys_bind_key.extend_from_slice(b"$keys");
-        let mut mapping_bind_key = key.to_vec();
-        mapping_bind_key.extend_from_slice(b"$mapping");
-
+        let (keys_bind_key, mapping_bind_key) = Self::bind_keys(key);
         Self {
             keys: Vec::<KeyEntry<K>>::bind_with(&keys_bind_key),
             mapping: Mapping::<K, ValueEntry<V>>::bind_with(&mapping_bind_key),
         }
     }
+
+    fn bind_nested(key: &[u8]) -> Self {
+        let (keys_bind_key, mapping_bind_key) = Self::bind_keys(key);
+        Self {
+            keys: Vec::<KeyEntry<K>>::bind_nested(&keys_bind_key),
+            mapping: Mapping::<K, ValueEntry<V>>::bind_nested(&mapping_bind_key),
+        }
+    }
+}
+
+impl<K, V> IterableMapping<K, V> {
+    /// Returns the keys which the keys and the values are bound to.
+    fn bind_keys(
+        key: &[u8],
+    ) -> (liquid_prelude::vec::Vec<u8>, liquid_prelude::vec::Vec<u8>) {
+        let mut keys_bind_key = key.to_vec();
+        keys_bind_key.extend_from_slice(b"$keys");
+        let mut mapping_bind_key = key.to_vec();
+        mapping_bind_key.extend_from_slice(b"$mapping");
+        (keys_bind_key, mapping_bind_key)
+    }
 }
 
 impl<K, V> Flush for IterableMapping<K, V>
@@ -240,6 +264,10 @@ where
         }
     }
 
+    /// Removes the `key` from the mapping, returns the value at the `key` if
+    /// the `key` was previously in the mapping.
+    ///
+    /// The removed key is replaced by the last key in the iteration order.
     pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
     where
         K: Borrow<Q>,
@@ -248,14 +276,81 @@ where
         let ret = self.mapping.remove(key);
 
         if let Some(ret) = ret {
-            let key_index = ret.key_index;
-            self.keys[key_index].deleted = true;
+            self.swap_remove_key(ret.key_index);
             return Some(ret.val);
         }
 
         None
     }
 
+    /// Removes all tombstones from the key list.
+    ///
+    /// # Note
+    ///
+    /// `remove` always frees the slot of the removed key, tombstones only exist
+    /// in key lists written by former versions, in which removed keys were just
+    /// marked as deleted. Since iteration skips tombstones one by one, this
+    /// method can be called once to make iteration over such key lists cost
+    /// proportional to the number of live keys again.
+    pub fn compact(&mut self) {
+        let mut i = 0;
+        while i < self.keys.len() {
+            if self.keys[i].deleted {
+                self.swap_remove_key(i);
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    /// Removes all entries of the mapping from storage.
+    ///
+    /// # Note
+    ///
+    /// This removes two storage keys per entry in a single call. For large
+    /// mappings, use `clear_batch` to free the storage over several calls.
+    pub fn clear(&mut self) {
+        self.clear_batch(self.keys.len());
+    }
+
+    /// Removes at most `n` entries of the mapping, starting from the last one
+    /// in the iteration order, and returns the number of entries left to be
+    /// removed, which also counts the tombstones left by former versions.
+    ///
+    /// Calling this method repeatedly until it returns `0` frees all storage
+    /// occupied by the mapping, except the lengths kept by a mapping bound to a
+    /// field of the storage struct to stay initialized. The removed values are
+    /// not loaded from storage.
+    pub fn clear_batch(&mut self, n: u32) -> u32 {
+        for _ in 0..n {
+            match self.keys.pop() {
+                Some(key_entry) => {
+                    if !key_entry.deleted {
+                        self.mapping.discard(&key_entry.key);
+                    }
+                }
+                None => break,
+            }
+        }
+        if self.keys.is_empty() {
+            self.keys.clear();
+        }
+        self.keys.len()
+    }
+
+    /// Removes the `n`-th entry of the key list by moving the last entry into
+    /// its slot, and fixes up the index recorded for the moved key.
+    fn swap_remove_key(&mut self, n: u32) {
+        self.keys.swap_remove(n);
+        if let Some(moved) = self.keys.get(n) {
+            if !moved.deleted {
+                if let Some(value_entry) = self.mapping.get_mut(&moved.key) {
+                    value_entry.key_index = n;
+                }
+            }
+        }
+    }
+
     pub fn contains_key<Q>(&self, key: &Q) -> bool
     where
         K: Borrow<Q>,
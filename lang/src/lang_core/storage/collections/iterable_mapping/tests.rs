@@ -11,7 +11,13 @@
 // limitations under the License.
 
 use super::IterableMapping;
-use crate::lang_core::storage::traits::Bind;
+use crate::lang_core::{
+    env,
+    storage::{
+        traits::{Bind, Flush},
+        Mapping, Vec as StorageVec,
+    },
+};
 use scale::Codec;
 
 fn new_empty<K: Codec, V: Codec>() -> IterableMapping<K, V> {
@@ -76,11 +82,146 @@ fn iter_works() {
     assert_eq!(iter.next(), Some((&"Dog".to_string(), &3)));
     assert_eq!(iter.next(), None);
 
+    map.remove(&"Alice".to_string());
+    let mut entries = map.iter().collect::<Vec<_>>();
+    entries.sort();
+    assert_eq!(
+        entries,
+        vec![(&"Bob".to_string(), &1), (&"Dog".to_string(), &3)]
+    );
+}
+
+#[test]
+fn remove_moves_last_key() {
+    let mut map = new_empty::<String, u8>();
+    for (i, name) in ["Alice", "Bob", "Charlie", "Dog"].iter().enumerate() {
+        map.insert(name.to_string(), i as u8);
+    }
+
+    // The last key takes the slot of the removed one.
     map.remove(&"Alice".to_string());
     let mut iter = map.iter();
+    assert_eq!(iter.next(), Some((&"Dog".to_string(), &3)));
     assert_eq!(iter.next(), Some((&"Bob".to_string(), &1)));
+    assert_eq!(iter.next(), Some((&"Charlie".to_string(), &2)));
+    assert_eq!(iter.next(), None);
+
+    // Removing the last key keeps the order of the others.
+    map.remove(&"Charlie".to_string());
+    let mut iter = map.iter();
+    assert_eq!(iter.next(), Some((&"Dog".to_string(), &3)));
+    assert_eq!(iter.next(), Some((&"Bob".to_string(), &1)));
+    assert_eq!(iter.next(), None);
+
+    // New keys are appended.
+    map.insert("Eve".to_string(), 4);
+    map.remove(&"Dog".to_string());
+    let mut iter = map.iter();
+    assert_eq!(iter.next(), Some((&"Eve".to_string(), &4)));
+    assert_eq!(iter.next(), Some((&"Bob".to_string(), &1)));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn remove_reuses_slots() {
+    let mut map = new_empty::<u32, u32>();
+    for round in 0..10 {
+        for i in 0..5 {
+            map.insert(i, round * i);
+        }
+        for i in 0..5 {
+            if i % 2 == 0 {
+                assert_eq!(map.remove(&i), Some(round * i));
+            }
+        }
+    }
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.iter().count(), 2);
+    map.insert(0, 42);
+    assert_eq!(map.iter().count(), 3);
+    assert_eq!(map.remove(&1), Some(9));
+    assert_eq!(map.remove(&3), Some(27));
+    assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&0, &42)]);
+    map.flush();
+
+    let keys = StorageVec::<(u32, bool)>::bind_with(b"var$keys");
+    assert_eq!(keys.len(), 1);
+}
+
+#[test]
+fn compact_legacy_tombstones() {
+    // Writes a key list in the legacy layout, in which `Alice` and `Charlie`
+    // were removed by marking them as deleted.
+    let mut keys = StorageVec::<(String, bool)>::bind_with(b"var$keys");
+    keys.initialize();
+    let mut mapping = Mapping::<String, (u32, u8)>::bind_with(b"var$mapping");
+    mapping.initialize();
+    for (i, (name, deleted)) in [
+        ("Alice", true),
+        ("Bob", false),
+        ("Charlie", true),
+        ("Dog", false),
+    ]
+    .iter()
+    .enumerate()
+    {
+        keys.push((name.to_string(), *deleted));
+        if !deleted {
+            mapping.insert(name.to_string(), (i as u32, i as u8));
+        }
+    }
+    keys.flush();
+    mapping.flush();
+
+    let mut map = IterableMapping::<String, u8>::bind_with(b"var");
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.iter().count(), 2);
+    map.compact();
+    let mut iter = map.iter();
     assert_eq!(iter.next(), Some((&"Dog".to_string(), &3)));
+    assert_eq!(iter.next(), Some((&"Bob".to_string(), &1)));
     assert_eq!(iter.next(), None);
+
+    assert_eq!(map.remove(&"Dog".to_string()), Some(3));
+    assert_eq!(map.get(&"Bob".to_string()), Some(&1));
+    map.flush();
+
+    let keys = StorageVec::<(String, bool)>::bind_with(b"var$keys");
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0], ("Bob".to_string(), false));
+}
+
+#[test]
+fn clear_batch_works() {
+    let mut map = new_empty::<u32, u32>();
+    map.extend((0..5).map(|i| (i, i * i)));
+    map.flush();
+
+    let mut map = IterableMapping::<u32, u32>::bind_with(b"var");
+    let (left, usage) = env::test::metered(|| map.clear_batch(3));
+    assert_eq!(left, 2);
+    // Only the popped keys are loaded, the removed values are not.
+    assert_eq!(usage.storage_reads, 5);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&0, &0), (&1, &1)]);
+    map.flush();
+    assert!(env::api::get_storage::<(u32, u32)>(b"var$mapping$\x02\0\0\0").is_err());
+
+    let mut map = IterableMapping::<u32, u32>::bind_with(b"var");
+    assert_eq!(map.len(), 2);
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.clear_batch(1), 0);
+    map.flush();
+    // The mapping is bound to a field, so its lengths are kept.
+    assert_eq!(env::api::get_storage::<u32>(b"var$keys").ok(), Some(0));
+    assert_eq!(env::api::get_storage::<u32>(b"var$mapping").ok(), Some(0));
+    assert!(env::api::get_storage::<(u32, u32)>(b"var$mapping$\0\0\0\0").is_err());
+
+    let mut map = IterableMapping::<u32, u32>::bind_with(b"var");
+    assert_eq!(map.insert(7, 49), None);
+    assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&7, &49)]);
 }
 
 #[test]
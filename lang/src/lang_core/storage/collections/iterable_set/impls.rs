@@ -0,0 +1,298 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::storage::{
+    Bind, Flush, Mapping, Vec,
+    You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+};
+use cfg_if::cfg_if;
+use core::borrow::Borrow;
+use scale::{Codec, Decode, Encode};
+
+/// A set of elements stored in the contract storage, which can be iterated.
+///
+/// The elements are stored in a `Vec` bound to the bound key followed by
+/// `$elems`, and the position of each element in that vector is stored in a
+/// `Mapping` bound to the bound key followed by `$index`. Removing an element
+/// moves the last element into its slot, hence the iteration order changes
+/// after removals.
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct IterableSet<T> {
+    elems: Vec<T>,
+    index: Mapping<T, u32>,
+}
+
+pub struct Iter<'a, T> {
+    iterable_set: &'a IterableSet<T>,
+    begin: u32,
+    end: u32,
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub(crate) fn new(iterable_set: &'a IterableSet<T>) -> Self {
+        Self {
+            iterable_set,
+            begin: 0,
+            end: iterable_set.len(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: Codec,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        debug_assert!(self.begin <= self.end);
+
+        if self.begin == self.end {
+            return None;
+        }
+
+        let ret = self.iterable_set.elems.get(self.begin);
+        self.begin += 1;
+        ret
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end - self.begin) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> where T: Codec {}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T>
+where
+    T: Codec,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        debug_assert!(self.begin <= self.end);
+
+        if self.begin == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        self.iterable_set.elems.get(self.end)
+    }
+}
+
+impl<T> Bind for IterableSet<T> {
+    fn bind_with(key: &[u8]) -> Self {
+        let (elems_bind_key, index_bind_key) = Self::bind_keys(key);
+        Self {
+            elems: Vec::<T>::bind_with(&elems_bind_key),
+            index: Mapping::<T, u32>::bind_with(&index_bind_key),
+        }
+    }
+
+    fn bind_nested(key: &[u8]) -> Self {
+        let (elems_bind_key, index_bind_key) = Self::bind_keys(key);
+        Self {
+            elems: Vec::<T>::bind_nested(&elems_bind_key),
+            index: Mapping::<T, u32>::bind_nested(&index_bind_key),
+        }
+    }
+}
+
+impl<T> IterableSet<T> {
+    /// Returns the keys which the elements and their indices are bound to.
+    fn bind_keys(
+        key: &[u8],
+    ) -> (liquid_prelude::vec::Vec<u8>, liquid_prelude::vec::Vec<u8>) {
+        let mut elems_bind_key = key.to_vec();
+        elems_bind_key.extend_from_slice(b"$elems");
+        let mut index_bind_key = key.to_vec();
+        index_bind_key.extend_from_slice(b"$index");
+        (elems_bind_key, index_bind_key)
+    }
+}
+
+impl<T> Flush for IterableSet<T>
+where
+    T: Codec,
+{
+    fn flush(&mut self) {
+        self.elems.flush();
+        self.index.flush();
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "contract")] {
+        use crate::lang_core::storage::Getter;
+
+        impl<T> Getter for IterableSet<T>
+        where
+            T: Codec,
+        {
+            type Index = T;
+            type Output = bool;
+
+            fn getter_impl(&self, index: Self::Index) -> Self::Output {
+                self.contains(&index)
+            }
+        }
+    }
+}
+
+impl<T> IterableSet<T> {
+    pub fn initialize(&mut self) {
+        self.elems.initialize();
+        self.index.initialize();
+    }
+
+    pub fn len(&self) -> u32 {
+        self.elems.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elems.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::<'_, T>::new(self)
+    }
+}
+
+impl<T> IterableSet<T>
+where
+    T: Codec,
+{
+    /// Adds a value to the set.
+    ///
+    /// Returns whether the value was newly inserted.
+    pub fn insert(&mut self, value: T) -> bool {
+        if self.index.contains_key(&value) {
+            return false;
+        }
+
+        let cloned_value = <T as Decode>::decode(&mut value.encode().as_slice()).unwrap();
+        self.index.insert(cloned_value, self.elems.len());
+        self.elems.push(value);
+        true
+    }
+
+    /// Removes a value from the set.
+    ///
+    /// Returns whether the value was present in the set. The removed value is
+    /// replaced by the last value in the iteration order.
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Encode + ?Sized,
+    {
+        let n = match self.index.remove(value) {
+            Some(n) => n,
+            None => return false,
+        };
+
+        self.elems.swap_remove(n);
+        if let Some(moved) = self.elems.get(n) {
+            *self.index.get_mut::<T>(moved).expect(
+                "[liquid_lang::IterableSet::remove] Error: expected moved value to be \
+                 existed in index",
+            ) = n;
+        }
+        true
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Encode + ?Sized,
+    {
+        self.index.contains_key(value)
+    }
+
+    /// Visits the values representing the difference, i.e., the values that
+    /// are in `self` but not in `other`.
+    pub fn difference<'a>(
+        &'a self,
+        other: &'a IterableSet<T>,
+    ) -> impl Iterator<Item = &'a T> + 'a {
+        self.iter().filter(move |value| !other.contains(*value))
+    }
+
+    /// Visits the values representing the intersection, i.e., the values that
+    /// are both in `self` and `other`.
+    pub fn intersection<'a>(
+        &'a self,
+        other: &'a IterableSet<T>,
+    ) -> impl Iterator<Item = &'a T> + 'a {
+        self.iter().filter(move |value| other.contains(*value))
+    }
+
+    /// Visits the values representing the union, i.e., all the values in
+    /// `self` or `other`, without duplicates.
+    pub fn union<'a>(
+        &'a self,
+        other: &'a IterableSet<T>,
+    ) -> impl Iterator<Item = &'a T> + 'a {
+        self.iter().chain(other.difference(self))
+    }
+
+    /// Returns `true` if `self` has no values in common with `other`.
+    pub fn is_disjoint(&self, other: &IterableSet<T>) -> bool {
+        if self.len() <= other.len() {
+            self.iter().all(|value| !other.contains(value))
+        } else {
+            other.iter().all(|value| !self.contains(value))
+        }
+    }
+
+    /// Returns `true` if all values of `self` are in `other`.
+    pub fn is_subset(&self, other: &IterableSet<T>) -> bool {
+        self.len() <= other.len() && self.iter().all(|value| other.contains(value))
+    }
+
+    /// Returns `true` if all values of `other` are in `self`.
+    pub fn is_superset(&self, other: &IterableSet<T>) -> bool {
+        other.is_subset(self)
+    }
+}
+
+impl<T> Extend<T> for IterableSet<T>
+where
+    T: Codec,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<'a, T> Extend<&'a T> for IterableSet<T>
+where
+    T: Codec + Copy + 'a,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+impl<T> You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage
+    for IterableSet<T>
+{
+    type Wrapped1 = T;
+    type Wrapped2 = ();
+}
@@ -0,0 +1,104 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::IterableSet;
+use crate::lang_core::storage::traits::{Bind, Flush};
+use scale::Codec;
+
+fn new_empty<T: Codec>() -> IterableSet<T> {
+    let mut set = IterableSet::<T>::bind_with(b"var");
+    set.initialize();
+    set
+}
+
+fn new_with(key: &[u8], values: &[u32]) -> IterableSet<u32> {
+    let mut set = IterableSet::<u32>::bind_with(key);
+    set.initialize();
+    set.extend(values);
+    set
+}
+
+#[test]
+fn empty() {
+    let set = new_empty::<String>();
+    assert_eq!(set.len(), 0);
+    assert_eq!(set.is_empty(), true);
+    assert_eq!(set.iter().next(), None);
+}
+
+#[test]
+fn insert_works() {
+    let mut set = new_empty::<String>();
+    assert_eq!(set.insert("Alice".to_string()), true);
+    assert_eq!(set.insert("Alice".to_string()), false);
+    assert_eq!(set.insert("Bob".to_string()), true);
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.contains("Alice"), true);
+    assert_eq!(set.contains("Charlie"), false);
+}
+
+#[test]
+fn iter_works() {
+    let mut set = new_empty::<String>();
+    set.insert("Alice".to_string());
+    set.insert("Bob".to_string());
+    set.insert("Charlie".to_string());
+    set.insert("Dog".to_string());
+    assert_eq!(set.remove("Alice"), true);
+    assert_eq!(set.remove("Alice"), false);
+
+    let mut iter = set.iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next(), Some(&"Dog".to_string()));
+    assert_eq!(iter.next_back(), Some(&"Charlie".to_string()));
+    assert_eq!(iter.next(), Some(&"Bob".to_string()));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn remove_works() {
+    let mut set = new_with(b"var", &[0, 1, 2, 3, 4]);
+    for i in (0..5).step_by(2) {
+        assert_eq!(set.remove(&i), true);
+    }
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.contains(&1), true);
+    assert_eq!(set.contains(&3), true);
+
+    set.insert(0);
+    assert_eq!(set.remove(&1), true);
+    assert_eq!(set.remove(&3), true);
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![&0]);
+    set.flush();
+
+    let set = IterableSet::<u32>::bind_with(b"var");
+    assert_eq!(set.len(), 1);
+    assert_eq!(set.contains(&0), true);
+    assert_eq!(set.contains(&1), false);
+}
+
+#[test]
+fn set_algebra_works() {
+    let a = new_with(b"a", &[1, 2, 3, 4]);
+    let b = new_with(b"b", &[3, 4, 5]);
+    let c = new_with(b"c", &[2, 3]);
+
+    assert_eq!(a.difference(&b).collect::<Vec<_>>(), vec![&1, &2]);
+    assert_eq!(a.intersection(&b).collect::<Vec<_>>(), vec![&3, &4]);
+    assert_eq!(a.union(&b).collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5]);
+
+    assert_eq!(c.is_subset(&a), true);
+    assert_eq!(a.is_superset(&c), true);
+    assert_eq!(c.is_subset(&b), false);
+    assert_eq!(a.is_disjoint(&b), false);
+    assert_eq!(new_with(b"d", &[6]).is_disjoint(&a), true);
+}
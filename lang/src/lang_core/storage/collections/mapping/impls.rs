@@ -10,14 +10,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::lang_core::storage::{
-    Bind, CachedCell, CachedChunk, Flush,
-    You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+use crate::lang_core::{
+    env::{self, backend::ContainerKind},
+    storage::{
+        Bind, CachedCell, CachedChunk, Flush, Storable,
+        You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+    },
 };
 use cfg_if::cfg_if;
 use core::{borrow::Borrow, marker::PhantomData};
 use scale::{Codec, Encode};
 
+/// A mapping stored in the contract storage.
+///
+/// The number of entries is stored at the bound key, and each value is stored
+/// at the bound key followed by `$` and the encoded key of the value.
+///
+/// Values can also be storage containers, e.g.,
+/// `Mapping<Address, Mapping<Address, u128>>`. Such values are never stored as
+/// a whole, instead, each of them is lazily bound with the key of its slot on
+/// the first access through `get`, `get_mut` or indexing, hence accessing
+/// `allowances[&a][&b]` only touches the slot of `b` in the inner mapping.
+/// Nested containers are always regarded as present, and they are not counted
+/// in the length of the outer mapping.
 #[cfg_attr(feature = "std", derive(Debug))]
 pub struct Mapping<K, V> {
     len: CachedCell<u32>,
@@ -27,22 +42,30 @@ pub struct Mapping<K, V> {
 
 impl<K, V> Bind for Mapping<K, V> {
     fn bind_with(key: &[u8]) -> Self {
+        env::api::bind_container(key, ContainerKind::Mapping);
+
         Self {
             len: CachedCell::<u32>::new(key),
             chunk: CachedChunk::<V>::new(key),
             marker: Default::default(),
         }
     }
+
+    fn bind_nested(key: &[u8]) -> Self {
+        let mut mapping = Self::bind_with(key);
+        mapping.len = CachedCell::<u32>::new_with_default(key, Default::default);
+        mapping
+    }
 }
 
 impl<K, V> Flush for Mapping<K, V>
 where
     K: Encode,
-    V: Encode,
+    V: Storable,
 {
     fn flush(&mut self) {
         self.len.flush();
-        self.chunk.flush();
+        V::flush_in(&mut self.chunk);
     }
 }
 
@@ -94,6 +117,30 @@ impl<K, V> Mapping<K, V> {
     }
 }
 
+impl<K, V> Mapping<K, V>
+where
+    K: Codec,
+    V: Storable,
+{
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Encode + ?Sized,
+    {
+        let encoded_key = key.encode();
+        V::get_in(&self.chunk, &encoded_key)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Encode + ?Sized,
+    {
+        let encoded_key = key.encode();
+        V::get_mut_in(&mut self.chunk, &encoded_key)
+    }
+}
+
 impl<K, V> Mapping<K, V>
 where
     K: Codec,
@@ -166,22 +213,23 @@ where
         ret
     }
 
-    pub fn get<Q>(&self, key: &Q) -> Option<&V>
-    where
-        K: Borrow<Q>,
-        Q: Encode + ?Sized,
-    {
-        let encoded_key = key.encode();
-        self.chunk.get(&encoded_key)
-    }
-
-    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    /// Removes a key which is known to be in the map without loading its
+    /// value. Once a map nested in another container becomes empty, the stored
+    /// length of the map is removed as well.
+    pub(crate) fn discard<Q>(&mut self, key: &Q)
     where
         K: Borrow<Q>,
         Q: Encode + ?Sized,
     {
-        let encoded_key = key.encode();
-        self.chunk.get_mut(&encoded_key)
+        self.chunk.remove(&key.encode());
+        let len = self.len.get_mut().expect(
+            "[liquid_lang::Mapping::discard] Error: expected `len` field to be existed \
+             in storage",
+        );
+        *len -= 1;
+        if *len == 0 && self.len.has_default() {
+            self.len.remove();
+        }
     }
 
     pub fn contains_key<Q>(&self, key: &Q) -> bool
@@ -197,7 +245,7 @@ where
 impl<'a, K, Q, V> core::ops::Index<&'a Q> for Mapping<K, V>
 where
     K: Codec + Borrow<Q>,
-    V: Codec,
+    V: Storable,
     Q: Encode + ?Sized,
 {
     type Output = V;
@@ -211,7 +259,7 @@ where
 impl<'a, K, Q, V> core::ops::IndexMut<&'a Q> for Mapping<K, V>
 where
     K: Codec + Borrow<Q>,
-    V: Codec,
+    V: Storable,
     Q: Encode + ?Sized,
 {
     fn index_mut(&mut self, index: &'a Q) -> &mut Self::Output {
@@ -11,7 +11,13 @@
 // limitations under the License.
 
 use super::Mapping;
-use crate::lang_core::storage::traits::Bind;
+use crate::lang_core::{
+    env::test,
+    storage::{
+        traits::{Bind, Flush},
+        Vec as StorageVec,
+    },
+};
 
 fn new_empty<K, V>() -> Mapping<K, V> {
     let mut map = Mapping::<K, V>::bind_with(b"var");
@@ -142,3 +148,73 @@ fn extend_works() {
         assert_eq!(map[&keys[i]], vals[i]);
     }
 }
+
+#[test]
+fn nested_mapping_works() {
+    let mut allowances = new_empty::<u8, Mapping<u8, u128>>();
+    allowances[&1].insert(2, 100);
+    allowances.get_mut(&1).unwrap().insert(3, 200);
+    allowances[&2].insert(1, 300);
+    assert_eq!(allowances[&1][&2], 100);
+    assert_eq!(allowances[&1].len(), 2);
+    assert_eq!(allowances[&2].get(&2), None);
+    assert_eq!(allowances[&3].len(), 0);
+    assert_eq!(allowances.len(), 0);
+    allowances.flush();
+
+    let mut allowances = Mapping::<u8, Mapping<u8, u128>>::bind_with(b"var");
+    let (_, usage) = test::metered(|| {
+        assert_eq!(allowances[&1][&3], 200);
+    });
+    assert_eq!(usage.storage_reads, 1);
+
+    let (_, usage) = test::metered(|| {
+        allowances[&2][&1] += 1;
+        allowances.flush();
+    });
+    assert_eq!(usage.storage_reads, 1);
+    assert_eq!(usage.storage_writes, 1);
+
+    let allowances = Mapping::<u8, Mapping<u8, u128>>::bind_with(b"var");
+    assert_eq!(allowances[&2][&1], 301);
+    assert_eq!(allowances[&3].get(&1), None);
+}
+
+#[test]
+fn nested_vec_works() {
+    let mut lists = new_empty::<String, StorageVec<u32>>();
+    lists[&"Alice".to_string()].extend(&[1, 2, 3]);
+    lists[&"Bob".to_string()].push(4);
+    lists.flush();
+
+    let mut lists = Mapping::<String, StorageVec<u32>>::bind_with(b"var");
+    assert_eq!(lists[&"Alice".to_string()].len(), 3);
+    assert_eq!(lists[&"Bob".to_string()].pop(), Some(4));
+    assert!(lists[&"Charlie".to_string()].is_empty());
+    lists.flush();
+
+    let lists = Mapping::<String, StorageVec<u32>>::bind_with(b"var");
+    assert_eq!(
+        lists[&"Alice".to_string()].iter().collect::<Vec<_>>(),
+        [&1, &2, &3]
+    );
+    assert!(lists[&"Bob".to_string()].is_empty());
+}
+
+#[test]
+fn nested_vec_clear_frees_storage() {
+    let mut lists = new_empty::<u8, StorageVec<u32>>();
+    lists[&1].extend(&[1, 2]);
+    lists.flush();
+    assert_eq!(test::dump_storage().len(), 4);
+
+    let mut lists = Mapping::<u8, StorageVec<u32>>::bind_with(b"var");
+    lists[&1].clear();
+    lists.flush();
+    // Only the length of the outer mapping is left.
+    let paths = test::dump_storage()
+        .into_iter()
+        .map(|entry| entry.path)
+        .collect::<Vec<_>>();
+    assert_eq!(paths, vec!["var.len"]);
+}
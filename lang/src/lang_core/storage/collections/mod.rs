@@ -10,10 +10,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod btree_mapping;
+mod deque;
 mod iterable_mapping;
+mod iterable_set;
 mod mapping;
+mod set;
 mod vec;
 
+pub use btree_mapping::BTreeMapping;
+pub use deque::Deque;
 pub use iterable_mapping::IterableMapping;
+pub use iterable_set::IterableSet;
 pub use mapping::Mapping;
+pub use set::Set;
 pub use vec::Vec;
@@ -0,0 +1,146 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::storage::{
+    Bind, Flush, Mapping, You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+};
+use cfg_if::cfg_if;
+use core::borrow::Borrow;
+use scale::{Codec, Encode};
+
+/// A set of elements stored in the contract storage.
+///
+/// The set shares the storage layout of `Mapping`: the number of elements is
+/// stored at the bound key, and each element is stored at the bound key
+/// followed by `$` and its encoding. Since the chain treats an empty value as
+/// an absent one, a one-byte marker is stored for every element.
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Set<T> {
+    members: Mapping<T, bool>,
+}
+
+impl<T> Bind for Set<T> {
+    fn bind_with(key: &[u8]) -> Self {
+        Self {
+            members: Mapping::<T, bool>::bind_with(key),
+        }
+    }
+
+    fn bind_nested(key: &[u8]) -> Self {
+        Self {
+            members: Mapping::<T, bool>::bind_nested(key),
+        }
+    }
+}
+
+impl<T> Flush for Set<T>
+where
+    T: Encode,
+{
+    fn flush(&mut self) {
+        self.members.flush();
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "contract")] {
+        use crate::lang_core::storage::Getter;
+
+        impl<T> Getter for Set<T>
+        where
+            T: Codec,
+        {
+            type Index = T;
+            type Output = bool;
+
+            fn getter_impl(&self, index: Self::Index) -> Self::Output {
+                self.contains(&index)
+            }
+        }
+    }
+}
+
+impl<T> Set<T> {
+    pub fn initialize(&mut self) {
+        self.members.initialize();
+    }
+
+    pub fn len(&self) -> u32 {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+}
+
+impl<T> Set<T>
+where
+    T: Codec,
+{
+    /// Adds a value to the set.
+    ///
+    /// Returns whether the value was newly inserted.
+    pub fn insert(&mut self, value: T) -> bool {
+        self.members.insert(value, true).is_none()
+    }
+
+    /// Removes a value from the set.
+    ///
+    /// Returns whether the value was present in the set.
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Encode + ?Sized,
+    {
+        self.members.remove(value).is_some()
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Encode + ?Sized,
+    {
+        self.members.contains_key(value)
+    }
+}
+
+impl<T> Extend<T> for Set<T>
+where
+    T: Codec,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<'a, T> Extend<&'a T> for Set<T>
+where
+    T: Codec + Copy + 'a,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+impl<T> You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage for Set<T> {
+    type Wrapped1 = T;
+    type Wrapped2 = ();
+}
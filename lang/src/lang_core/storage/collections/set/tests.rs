@@ -0,0 +1,85 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Set;
+use crate::lang_core::storage::{
+    traits::{Bind, Flush},
+    Mapping,
+};
+use scale::Codec;
+
+fn new_empty<T: Codec>() -> Set<T> {
+    let mut set = Set::<T>::bind_with(b"var");
+    set.initialize();
+    set
+}
+
+#[test]
+fn empty() {
+    let set = new_empty::<String>();
+    assert_eq!(set.len(), 0);
+    assert_eq!(set.is_empty(), true);
+    assert_eq!(set.contains("Alice"), false);
+}
+
+#[test]
+fn insert_works() {
+    let mut set = new_empty::<String>();
+    assert_eq!(set.insert("Alice".to_string()), true);
+    assert_eq!(set.len(), 1);
+    assert_eq!(set.insert("Alice".to_string()), false);
+    assert_eq!(set.len(), 1);
+    assert_eq!(set.insert("Bob".to_string()), true);
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.contains("Alice"), true);
+    assert_eq!(set.contains("Bob"), true);
+    assert_eq!(set.contains("Charlie"), false);
+}
+
+#[test]
+fn remove_works() {
+    let mut set = new_empty::<String>();
+    set.insert("Alice".to_string());
+    set.insert("Bob".to_string());
+    assert_eq!(set.remove("Alice"), true);
+    assert_eq!(set.remove("Alice"), false);
+    assert_eq!(set.len(), 1);
+    assert_eq!(set.contains("Alice"), false);
+    assert_eq!(set.contains("Bob"), true);
+}
+
+#[test]
+fn extend_works() {
+    let mut set = new_empty::<u8>();
+    set.extend(&[1, 2, 2, 3]);
+    assert_eq!(set.len(), 3);
+    set.extend(vec![3, 4]);
+    assert_eq!(set.len(), 4);
+    assert!((1..=4).all(|i| set.contains(&i)));
+}
+
+#[test]
+fn shares_mapping_layout() {
+    let mut set = new_empty::<u8>();
+    set.extend(&[1, 2]);
+    set.remove(&1);
+    set.flush();
+
+    let mapping = Mapping::<u8, bool>::bind_with(b"var");
+    assert_eq!(mapping.len(), 1);
+    assert_eq!(mapping.get(&2), Some(&true));
+    assert_eq!(mapping.get(&1), None);
+
+    let set = Set::<u8>::bind_with(b"var");
+    assert_eq!(set.len(), 1);
+    assert_eq!(set.contains(&2), true);
+}
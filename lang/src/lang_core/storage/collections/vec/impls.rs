@@ -10,11 +10,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::lang_core::storage::{
-    Bind, CachedCell, CachedChunk, Flush,
-    You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+use crate::lang_core::{
+    env::{self, backend::ContainerKind},
+    storage::{
+        Bind, CachedCell, CachedChunk, Flush, MigrateFrom,
+        You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+    },
 };
 use cfg_if::cfg_if;
+use core::cmp::Ordering;
+use liquid_prelude::vec;
 use scale::{Codec, Encode};
 
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -84,11 +89,19 @@ where
 
 impl<T> Bind for Vec<T> {
     fn bind_with(key: &[u8]) -> Self {
+        env::api::bind_container(key, ContainerKind::Vec);
+
         Self {
             len: CachedCell::<u32>::new(key),
             chunk: CachedChunk::<T>::new(key),
         }
     }
+
+    fn bind_nested(key: &[u8]) -> Self {
+        let mut vec = Self::bind_with(key);
+        vec.len = CachedCell::<u32>::new_with_default(key, Default::default);
+        vec
+    }
 }
 
 impl<T> Flush for Vec<T>
@@ -269,6 +282,267 @@ where
             self.chunk.put(&n.to_le_bytes(), last_elem)
         }
     }
+
+    /// Returns a reference to the first element of the vector, or `None` if
+    /// it is empty.
+    pub fn first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a mutable reference to the first element of the vector, or
+    /// `None` if it is empty.
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Returns a reference to the last element of the vector, or `None` if it
+    /// is empty.
+    pub fn last(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|n| self.get(n))
+    }
+
+    /// Returns a mutable reference to the last element of the vector, or
+    /// `None` if it is empty.
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.len().checked_sub(1).and_then(move |n| self.get_mut(n))
+    }
+
+    /// Inserts an element at position `n` within the vector, shifting all
+    /// elements after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// If `n > len`.
+    pub fn insert(&mut self, n: u32, val: T) {
+        let len = self.len();
+        if n > len {
+            panic!(
+                "[liquid_lang::Vec::insert] Error: insertion index (is {}) should be <= \
+                 len (is {})",
+                n, len
+            );
+        }
+
+        let mut carry = val;
+        for i in n..len {
+            carry = self.chunk.put(&i.to_le_bytes(), carry).expect(
+                "[liquid_lang::Vec::insert] Error: expected `Some` value since index is \
+                 within bounds",
+            );
+        }
+        self.push(carry);
+    }
+
+    /// Removes the `n`-th element from the vector and returns it, shifting all
+    /// elements after it to the left.
+    /// Returns `None` and does not remove if `n` is out of bounds.
+    ///
+    /// # Note
+    ///
+    /// This preserves ordering, but is O(n). If ordering does not matter, use
+    /// `swap_remove` instead.
+    pub fn remove(&mut self, n: u32) -> Option<T> {
+        self.within_bounds(n)?;
+
+        let mut carry = self.pop().expect(
+            "[liquid_lang::Vec::remove] Error: expected `Some` value since vector is \
+             not empty",
+        );
+        for i in (n..self.len()).rev() {
+            carry = self.chunk.put(&i.to_le_bytes(), carry).expect(
+                "[liquid_lang::Vec::remove] Error: expected `Some` value since index is \
+                 within bounds",
+            );
+        }
+        Some(carry)
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and removing the
+    /// rest from storage.
+    ///
+    /// Has no effect if `len` is greater than or equal to the current length.
+    /// The removed elements are not loaded from storage.
+    pub fn truncate(&mut self, len: u32) {
+        let cur_len = self.len.get_mut().expect(
+            "[liquid_lang::Vec::truncate] Error: expected `len` field to be existed in \
+             storage",
+        );
+        while *cur_len > len {
+            *cur_len -= 1;
+            self.chunk.remove(&cur_len.to_le_bytes());
+        }
+    }
+
+    /// Removes all elements of the vector from storage, including the stored
+    /// length of a nested vector.
+    ///
+    /// # Note
+    ///
+    /// This removes one storage key per element in a single call. For large
+    /// vectors, use `clear_batch` to free the storage over several calls.
+    pub fn clear(&mut self) {
+        self.clear_batch(self.len());
+    }
+
+    /// Removes at most `n` elements from the back of the vector, and returns
+    /// the number of elements left.
+    ///
+    /// Once a vector nested in another container becomes empty, the stored
+    /// length of the vector is removed as well, hence calling this method
+    /// repeatedly until it returns `0` frees all storage occupied by the
+    /// vector. A vector bound to a field of the storage struct keeps its length
+    /// as `0` to stay initialized. The removed elements are not loaded from
+    /// storage, and elements which are storage containers themselves are not
+    /// cleared.
+    pub fn clear_batch(&mut self, n: u32) -> u32 {
+        let len = self.len();
+        self.truncate(len.saturating_sub(n));
+        if n >= len && self.len.has_default() {
+            self.len.remove();
+        }
+        self.len()
+    }
+
+    /// Retains only the elements specified by the predicate, i.e., removes all
+    /// elements `e` for which `f(&e)` returns `false`.
+    ///
+    /// This method operates in place, visits each element exactly once in the
+    /// original order and preserves the order of the retained elements.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let len = self.len();
+        let mut kept = 0;
+        for i in 0..len {
+            let index = i.to_le_bytes();
+            let retained = f(self.chunk.get(&index).expect(
+                "[liquid_lang::Vec::retain] Error: expected `Some` value since index is \
+                 within bounds",
+            ));
+            if !retained {
+                continue;
+            }
+
+            if kept != i {
+                let val = self.chunk.take(&index).expect(
+                    "[liquid_lang::Vec::retain] Error: expected `Some` value since \
+                     index is within bounds",
+                );
+                self.chunk.set(&kept.to_le_bytes(), val);
+            }
+            kept += 1;
+        }
+        self.truncate(kept);
+    }
+
+    /// Binary searches this sorted vector with a comparator function.
+    ///
+    /// If the value is found then `Ok` is returned, containing the index of the
+    /// matching element. If the value is not found then `Err` is returned,
+    /// containing the index where a matching element could be inserted while
+    /// maintaining sorted order. Only O(log n) elements are loaded from storage.
+    pub fn binary_search_by<F>(&self, mut f: F) -> Result<u32, u32>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        let mut lo = 0;
+        let mut hi = self.len();
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let elem = self.get(mid).expect(
+                "[liquid_lang::Vec::binary_search_by] Error: expected `Some` value \
+                 since index is within bounds",
+            );
+            match f(elem) {
+                Ordering::Less => lo = mid + 1,
+                Ordering::Greater => hi = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+        }
+        Err(lo)
+    }
+
+    /// Binary searches this sorted vector with a key extraction function.
+    ///
+    /// See `binary_search_by` for the meaning of the returned value.
+    pub fn binary_search_by_key<B, F>(&self, b: &B, mut f: F) -> Result<u32, u32>
+    where
+        F: FnMut(&T) -> B,
+        B: Ord,
+    {
+        self.binary_search_by(|elem| f(elem).cmp(b))
+    }
+
+    /// Binary searches this sorted vector for a given element.
+    ///
+    /// See `binary_search_by` for the meaning of the returned value.
+    pub fn binary_search(&self, x: &T) -> Result<u32, u32>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|elem| elem.cmp(x))
+    }
+
+    /// Sorts the vector with a comparator function.
+    ///
+    /// This sort is stable. Every element is loaded to be compared, but only
+    /// the elements whose position changed are written back to storage.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let len = self.len();
+        let mut order = (0..len).collect::<vec::Vec<u32>>();
+        order.sort_by(|&a, &b| {
+            let a = self.chunk.get(&a.to_le_bytes()).expect(
+                "[liquid_lang::Vec::sort_by] Error: expected `Some` value since index \
+                 is within bounds",
+            );
+            let b = self.chunk.get(&b.to_le_bytes()).expect(
+                "[liquid_lang::Vec::sort_by] Error: expected `Some` value since index \
+                 is within bounds",
+            );
+            compare(a, b)
+        });
+
+        let moved = order
+            .into_iter()
+            .zip(0..len)
+            .filter(|(from, to)| from != to)
+            .map(|(from, to)| {
+                let val = self.chunk.take(&from.to_le_bytes()).expect(
+                    "[liquid_lang::Vec::sort_by] Error: expected `Some` value since \
+                     index is within bounds",
+                );
+                (to, val)
+            })
+            .collect::<vec::Vec<_>>();
+        for (to, val) in moved {
+            self.chunk.set(&to.to_le_bytes(), val);
+        }
+    }
+
+    /// Sorts the vector with a key extraction function.
+    ///
+    /// See `sort_by` for the details.
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Sorts the vector.
+    ///
+    /// See `sort_by` for the details.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(|a, b| a.cmp(b))
+    }
 }
 
 impl<T> Extend<T> for Vec<T>
@@ -321,6 +595,21 @@ where
     }
 }
 
+impl<Old, New> MigrateFrom<Vec<Old>> for Vec<New>
+where
+    Old: Codec,
+    New: Codec + From<Old>,
+{
+    fn migrate_from(&mut self, mut old: Vec<Old>) {
+        for i in 0..old.len() {
+            let index = i.to_le_bytes();
+            if let Some(val) = old.chunk.take(&index) {
+                self.chunk.set(&index, New::from(val));
+            }
+        }
+    }
+}
+
 impl<T> You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage for Vec<T> {
     type Wrapped1 = T;
     type Wrapped2 = ();
@@ -11,7 +11,13 @@
 // limitations under the License.
 
 use super::Vec;
-use crate::lang_core::storage::traits::Bind;
+use crate::lang_core::{
+    env,
+    storage::{
+        traits::{Bind, Flush},
+        MigrateFrom,
+    },
+};
 
 fn new_empty_vec() -> Vec<u8> {
     let mut vec = Vec::<u8>::bind_with(b"vec");
@@ -132,6 +138,13 @@ fn out_of_bounds_1() {
     vec[5] = 5;
 }
 
+#[test]
+#[should_panic]
+fn uninitialized() {
+    let vec = Vec::<u8>::bind_with(b"vec");
+    let _ = vec.len();
+}
+
 #[test]
 fn mutate_with() {
     let mut vec = new_filled_vec();
@@ -211,3 +224,163 @@ fn extend() {
         assert_eq!(vec[i], i as u8);
     }
 }
+
+#[test]
+fn first_and_last() {
+    let vec = new_empty_vec();
+    assert_eq!(vec.first(), None);
+    assert_eq!(vec.last(), None);
+
+    let mut vec = new_filled_vec();
+    assert_eq!(vec.first(), Some(&0x56));
+    assert_eq!(vec.last(), Some(&0x41));
+    *vec.first_mut().unwrap() = 0;
+    *vec.last_mut().unwrap() = 3;
+    assert_eq!(vec[0], 0);
+    assert_eq!(vec[3], 3);
+}
+
+#[test]
+fn insert() {
+    let mut vec = new_filled_vec();
+    vec.insert(1, 0);
+    vec.insert(5, 5);
+    vec.insert(0, 1);
+    assert_eq!(
+        vec.iter().collect::<std::vec::Vec<_>>(),
+        [&1, &0x56, &0, &0x49, &0x54, &0x41, &5]
+    );
+}
+
+#[test]
+#[should_panic]
+fn insert_out_of_bounds() {
+    let mut vec = new_filled_vec();
+    vec.insert(5, 5);
+}
+
+#[test]
+fn remove() {
+    let mut vec = new_filled_vec();
+    assert_eq!(vec.remove(4), None);
+    assert_eq!(vec.remove(1), Some(0x49));
+    assert_eq!(vec.remove(2), Some(0x41));
+    assert_eq!(vec.iter().collect::<std::vec::Vec<_>>(), [&0x56, &0x54]);
+    assert_eq!(vec.remove(0), Some(0x56));
+    assert_eq!(vec.remove(0), Some(0x54));
+    assert_eq!(vec.remove(0), None);
+}
+
+#[test]
+fn truncate_clears_storage() {
+    let mut vec = new_filled_vec();
+    vec.flush();
+
+    let mut vec = Vec::<u8>::bind_with(b"vec");
+    vec.truncate(5);
+    assert_eq!(vec.len(), 4);
+    vec.truncate(2);
+    assert_eq!(vec.len(), 2);
+    assert_eq!(vec.get(2), None);
+    vec.flush();
+    assert!(env::api::get_storage::<u8>(b"vec$\x02\0\0\0").is_err());
+    assert!(env::api::get_storage::<u8>(b"vec$\x03\0\0\0").is_err());
+
+    let mut vec = Vec::<u8>::bind_with(b"vec");
+    assert_eq!(vec.iter().collect::<std::vec::Vec<_>>(), [&0x56, &0x49]);
+    vec.clear();
+    assert!(vec.is_empty());
+    vec.flush();
+    assert!(env::api::get_storage::<u8>(b"vec$\0\0\0\0").is_err());
+}
+
+#[test]
+fn clear_batch_works() {
+    let mut vec = new_empty_vec();
+    vec.extend(&[1, 2, 3, 4, 5]);
+    vec.flush();
+
+    let mut vec = Vec::<u8>::bind_with(b"vec");
+    assert_eq!(vec.clear_batch(2), 3);
+    vec.flush();
+    assert!(env::api::get_storage::<u8>(b"vec$\x03\0\0\0").is_err());
+    assert!(env::api::get_storage::<u8>(b"vec$\x04\0\0\0").is_err());
+
+    let mut vec = Vec::<u8>::bind_with(b"vec");
+    assert_eq!(vec.iter().collect::<std::vec::Vec<_>>(), [&1, &2, &3]);
+    assert_eq!(vec.clear_batch(2), 1);
+    assert_eq!(vec.clear_batch(2), 0);
+    assert_eq!(vec.clear_batch(2), 0);
+    vec.flush();
+    // The vector is bound to a field, so its length is kept.
+    assert_eq!(env::api::get_storage::<u32>(b"vec").ok(), Some(0));
+    assert!(env::api::get_storage::<u8>(b"vec$\0\0\0\0").is_err());
+
+    let mut vec = Vec::<u8>::bind_with(b"vec");
+    assert!(vec.is_empty());
+    vec.push(6);
+    vec.flush();
+    assert_eq!(env::api::get_storage::<u32>(b"vec").ok(), Some(1));
+}
+
+#[test]
+fn retain() {
+    let mut vec = new_empty_vec();
+    vec.extend(&[1, 2, 3, 4, 5, 6]);
+    vec.retain(|x| x % 2 == 0);
+    assert_eq!(vec.iter().collect::<std::vec::Vec<_>>(), [&2, &4, &6]);
+    vec.flush();
+
+    let vec = Vec::<u8>::bind_with(b"vec");
+    assert_eq!(vec.iter().collect::<std::vec::Vec<_>>(), [&2, &4, &6]);
+    assert!(env::api::get_storage::<u8>(b"vec$\x03\0\0\0").is_err());
+}
+
+#[test]
+fn binary_search() {
+    let mut vec = new_empty_vec();
+    vec.extend(&[1, 3, 5, 7]);
+    assert_eq!(vec.binary_search(&5), Ok(2));
+    assert_eq!(vec.binary_search(&0), Err(0));
+    assert_eq!(vec.binary_search(&4), Err(2));
+    assert_eq!(vec.binary_search(&8), Err(4));
+    assert_eq!(vec.binary_search_by_key(&14, |x| x * 2), Ok(3));
+}
+
+#[test]
+fn sort() {
+    let mut vec = new_filled_vec();
+    vec.sort();
+    assert_eq!(
+        vec.iter().collect::<std::vec::Vec<_>>(),
+        [&0x41, &0x49, &0x54, &0x56]
+    );
+    vec.flush();
+
+    let mut vec = Vec::<u8>::bind_with(b"vec");
+    vec.extend(&[0x40, 0x50]);
+    vec.sort_by_key(|x| x / 0x10);
+    assert_eq!(
+        vec.iter().collect::<std::vec::Vec<_>>(),
+        [&0x41, &0x49, &0x40, &0x54, &0x56, &0x50]
+    );
+    vec.sort_by(|a, b| b.cmp(a));
+    assert_eq!(
+        vec.iter().collect::<std::vec::Vec<_>>(),
+        [&0x56, &0x54, &0x50, &0x49, &0x41, &0x40]
+    );
+}
+
+#[test]
+fn migrate_from_works() {
+    let mut vec = new_empty_vec();
+    vec.extend(&[1, 2, 3]);
+    vec.flush();
+
+    let mut vec = Vec::<u32>::bind_with(b"vec");
+    vec.migrate_from(Vec::<u8>::bind_with(b"vec"));
+    vec.flush();
+
+    let vec = Vec::<u32>::bind_with(b"vec");
+    assert_eq!(vec.iter().collect::<std::vec::Vec<_>>(), [&1, &2, &3]);
+}
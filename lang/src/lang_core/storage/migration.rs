@@ -0,0 +1,39 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::env;
+
+/// The key at which the schema version of the contract storage is recorded.
+pub const SCHEMA_VERSION_KEY: &[u8] = b"__liquid_schema_version";
+
+/// Returns the schema version recorded in the contract storage, or `0` if no
+/// schema version has been recorded yet.
+pub fn schema_version() -> u32 {
+    env::api::get_storage::<u32>(SCHEMA_VERSION_KEY).unwrap_or(0)
+}
+
+#[doc(hidden)]
+pub fn set_schema_version(version: u32) {
+    env::api::set_storage(SCHEMA_VERSION_KEY, &version);
+}
+
+/// Storage containers which can take over the data written by a container of
+/// type `Old` bound with the same key, converting the stored values on the way.
+///
+/// This trait is used to migrate the fields marked with
+/// `#[liquid(migrate_from = "...")]` in the storage struct, e.g., a field of
+/// type `storage::Value<u64>` marked with
+/// `#[liquid(migrate_from = "storage::Value<u32>")]` is migrated by reading
+/// the stored value as `u32` and writing it back as `u64`.
+pub trait MigrateFrom<Old> {
+    fn migrate_from(&mut self, old: Old);
+}
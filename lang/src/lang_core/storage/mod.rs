@@ -16,17 +16,22 @@ mod cache_entry;
 mod cell;
 mod chunk;
 mod collections;
+mod migration;
 mod traits;
 mod value;
 
 pub use self::{
-    collections::{IterableMapping, Mapping, Vec},
+    collections::{BTreeMapping, Deque, IterableMapping, IterableSet, Mapping, Set, Vec},
+    migration::{schema_version, set_schema_version, MigrateFrom, SCHEMA_VERSION_KEY},
     traits::*,
     value::Value,
 };
 
+#[doc(hidden)]
+pub use self::chunk::CachedChunk;
+
 use self::{
     cache_entry::CacheEntry,
     cell::{CachedCell, TypedCell},
-    chunk::{CachedChunk, TypedChunk},
+    chunk::TypedChunk,
 };
@@ -10,7 +10,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::{
+    BTreeMapping, CachedChunk, Deque, IterableMapping, IterableSet, Mapping, Set, Vec,
+};
 use cfg_if::cfg_if;
+use scale::Codec;
 
 pub trait Flush {
     fn flush(&mut self) {}
@@ -22,8 +26,96 @@ pub trait New {
 
 pub trait Bind {
     fn bind_with(key: &[u8]) -> Self;
+
+    /// Binds a container nested in a slot of another container to `key`.
+    ///
+    /// Nested containers can't be initialized in the constructor, hence they
+    /// are regarded as empty rather than uninitialized when nothing is stored
+    /// at `key`. Containers bound to fields of the storage struct still need
+    /// to be initialized explicitly.
+    fn bind_nested(key: &[u8]) -> Self
+    where
+        Self: Sized,
+    {
+        Self::bind_with(key)
+    }
+}
+
+/// Types which can be stored in the slots of storage containers.
+///
+/// Encodable values are stored as a whole in a single slot, while storage
+/// containers are lazily bound with the key of the slot, so that accessing
+/// an element of a nested container only touches the slot of that element.
+pub trait Storable: Sized {
+    #[doc(hidden)]
+    fn get_in<'a>(chunk: &'a CachedChunk<Self>, index: &[u8]) -> Option<&'a Self>;
+
+    #[doc(hidden)]
+    fn get_mut_in<'a>(
+        chunk: &'a mut CachedChunk<Self>,
+        index: &[u8],
+    ) -> Option<&'a mut Self>;
+
+    #[doc(hidden)]
+    fn flush_in(chunk: &mut CachedChunk<Self>);
+}
+
+impl<T> Storable for T
+where
+    T: Codec,
+{
+    fn get_in<'a>(chunk: &'a CachedChunk<Self>, index: &[u8]) -> Option<&'a Self> {
+        chunk.get(index)
+    }
+
+    fn get_mut_in<'a>(
+        chunk: &'a mut CachedChunk<Self>,
+        index: &[u8],
+    ) -> Option<&'a mut Self> {
+        chunk.get_mut(index)
+    }
+
+    fn flush_in(chunk: &mut CachedChunk<Self>) {
+        chunk.flush();
+    }
 }
 
+macro_rules! impl_storable_for_containers {
+    ($($container:ident<$($param:ident),+>),+) => {
+        $(
+            impl<$($param),+> Storable for $container<$($param),+>
+            where
+                Self: Bind + Flush,
+            {
+                fn get_in<'a>(chunk: &'a CachedChunk<Self>, index: &[u8]) -> Option<&'a Self> {
+                    Some(chunk.bind(index))
+                }
+
+                fn get_mut_in<'a>(
+                    chunk: &'a mut CachedChunk<Self>,
+                    index: &[u8],
+                ) -> Option<&'a mut Self> {
+                    Some(chunk.bind_mut(index))
+                }
+
+                fn flush_in(chunk: &mut CachedChunk<Self>) {
+                    chunk.flush_bound();
+                }
+            }
+        )+
+    };
+}
+
+impl_storable_for_containers!(
+    Vec<T>,
+    Deque<T>,
+    Set<T>,
+    IterableSet<T>,
+    Mapping<K, V>,
+    IterableMapping<K, V>,
+    BTreeMapping<K, V>
+);
+
 cfg_if! {
     if #[cfg(feature = "contract")] {
         pub trait Getter {
@@ -10,9 +10,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::lang_core::storage::{
-    Bind, CachedCell, Flush,
-    You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+use crate::lang_core::{
+    env::{self, backend::ContainerKind},
+    storage::{
+        Bind, CachedCell, Flush, MigrateFrom,
+        You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+    },
 };
 use cfg_if::cfg_if;
 use scale::Codec;
@@ -27,6 +30,8 @@ where
     T: Codec,
 {
     fn bind_with(key: &[u8]) -> Self {
+        env::api::bind_container(key, ContainerKind::Value);
+
         Self {
             cell: CachedCell::new(key),
         }
@@ -273,6 +278,18 @@ where
     }
 }
 
+impl<Old, New> MigrateFrom<Value<Old>> for Value<New>
+where
+    Old: Codec,
+    New: Codec + From<Old>,
+{
+    fn migrate_from(&mut self, mut old: Value<Old>) {
+        if let Some(old_val) = old.cell.take() {
+            self.set(New::from(old_val));
+        }
+    }
+}
+
 impl<T> You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage for Value<T> {
     type Wrapped1 = T;
     type Wrapped2 = ();
@@ -407,4 +424,18 @@ mod tests {
         let v2 = Value::<i32>::bind_with(b"v");
         assert_eq!(*v2, 3);
     }
+
+    #[test]
+    fn test_migrate_from() {
+        let mut v1 = Value::<u32>::bind_with(b"v");
+        v1.set(42);
+        v1.flush();
+
+        let mut v2 = Value::<u64>::bind_with(b"v");
+        v2.migrate_from(Value::<u32>::bind_with(b"v"));
+        v2.flush();
+
+        let v3 = Value::<u64>::bind_with(b"v");
+        assert_eq!(*v3, 42);
+    }
 }
@@ -17,6 +17,8 @@
 
 mod dispatch_error;
 mod env_access;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
 pub mod intrinsics;
 mod lang_core;
 #[cfg(feature = "std")]
@@ -39,7 +41,7 @@ pub mod precompiled {
     pub use super::lang_core::precompiled::*;
 }
 
-pub use liquid_lang_macro::InOut;
+pub use liquid_lang_macro::{InOut, StorageLayout};
 
 use cfg_if::cfg_if;
 
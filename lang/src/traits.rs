@@ -270,6 +270,32 @@ where
 {
 }
 
+// Storage containers can be nested in `storage::Mapping`, in which case they
+// are checked recursively.
+macro_rules! impl_state_trait_for_containers {
+    ($($container:ident<$($param:ident),+>),+) => {
+        $(
+            #[cfg(feature = "contract")]
+            impl<$($param),+> You_Should_Use_An_Valid_State_Type
+                for crate::storage::$container<$($param),+>
+            where
+                $($param: You_Should_Use_An_Valid_State_Type,)+
+            {
+            }
+        )+
+    };
+}
+
+impl_state_trait_for_containers!(
+    Vec<T>,
+    Deque<T>,
+    Set<T>,
+    IterableSet<T>,
+    Mapping<K, V>,
+    IterableMapping<K, V>,
+    BTreeMapping<K, V>
+);
+
 macro_rules! impl_topic_trait {
     ($($t:ty),*) => {
         $(
@@ -36,6 +36,10 @@ fn compile_tests() {
     t.pass("tests/contract/ui/pass/20-vec-unit-return.rs");
     t.pass("tests/contract/ui/pass/21-tuple-unit-return.rs");
     t.pass("tests/contract/ui/pass/22-many-inputs.rs");
+    t.pass("tests/contract/ui/pass/23-nested-containers.rs");
+    t.pass("tests/contract/ui/pass/24-storage-migration.rs");
+    t.pass("tests/contract/ui/pass/25-interface-deploy.rs");
+    t.pass("tests/contract/ui/pass/26-interface-try-call.rs");
     t.compile_fail("tests/contract/ui/fail/01-constructor-returns.rs");
     t.compile_fail("tests/contract/ui/fail/02-missing-constructor.rs");
     t.compile_fail("tests/contract/ui/fail/03-multiple-constructors.rs");
@@ -86,5 +90,11 @@ fn compile_tests() {
     t.compile_fail("tests/contract/ui/fail/48-overriding-interface.rs");
     t.compile_fail("tests/contract/ui/fail/49-invalid-state-type.rs");
     t.compile_fail("tests/contract/ui/fail/50-contract-redefined.rs");
-    t.compile_fail("tests/contract/ui/fail/51-define-interface-in-contract.rs")
+    t.compile_fail("tests/contract/ui/fail/51-define-interface-in-contract.rs");
+    t.compile_fail("tests/contract/ui/fail/52-migrate-without-schema-version.rs");
+    t.compile_fail("tests/contract/ui/fail/53-schema-version-without-migration-guard.rs");
+    t.compile_fail("tests/contract/ui/fail/54-migrate-from-unsupported-container.rs");
+    t.compile_fail("tests/contract/ui/fail/55-undefined-migration-guard.rs");
+    t.compile_fail("tests/contract/ui/fail/56-reserved-interface-deploy.rs");
+    t.compile_fail("tests/contract/ui/fail/57-reserved-interface-try-fn.rs")
 }
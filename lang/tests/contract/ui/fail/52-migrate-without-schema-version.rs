@@ -0,0 +1,22 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Noop {
+        #[liquid(migrate_from = "storage::Value<u32>")]
+        value: storage::Value<u64>,
+    }
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn noop(&self) {}
+    }
+}
+
+fn main() {}
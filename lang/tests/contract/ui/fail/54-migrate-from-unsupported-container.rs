@@ -0,0 +1,28 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(storage)]
+    #[liquid(schema_version = 2)]
+    #[liquid(migration_guard = "is_owner")]
+    struct Noop {
+        #[liquid(migrate_from = "storage::Mapping<u32, u32>")]
+        balances: storage::Mapping<u32, u64>,
+    }
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn noop(&self) {}
+
+        fn is_owner(&self) -> bool {
+            true
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(storage)]
+    #[liquid(schema_version = 2)]
+    #[liquid(migration_guard = "is_owner")]
+    struct Noop {
+        value: storage::Value<u64>,
+    }
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn noop(&self) {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use liquid_lang as liquid;
+
+#[liquid::interface(name = auto)]
+mod vault {
+    extern "liquid" {
+        fn new(&mut self, limit: u64);
+
+        fn deploy(&mut self, amount: u64);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use liquid_lang as liquid;
+
+#[liquid::interface(name = auto)]
+mod foo {
+    extern "liquid" {
+        fn bar(&self) -> u32;
+
+        fn try_bar(&self) -> u32;
+    }
+}
+
+fn main() {}
@@ -14,6 +14,10 @@ mod noop {
         pub b: storage::Vec<bool>,
         pub c: storage::Mapping<String, bool>,
         pub d: storage::IterableMapping<String, bool>,
+        pub e: storage::BTreeMapping<String, bool>,
+        pub f: storage::Set<String>,
+        pub g: storage::IterableSet<String>,
+        pub h: storage::Deque<bool>,
     }
 
     #[liquid(methods)]
@@ -39,6 +43,26 @@ mod noop {
             #[allow(deprecated)]
             self.d(String::from(""))
         }
+
+        pub fn noop_4(&self) -> bool {
+            #[allow(deprecated)]
+            self.e(String::from(""))
+        }
+
+        pub fn noop_5(&self) -> bool {
+            #[allow(deprecated)]
+            self.f(String::from(""))
+        }
+
+        pub fn noop_6(&self) -> bool {
+            #[allow(deprecated)]
+            self.g(String::from(""))
+        }
+
+        pub fn noop_7(&self) -> bool {
+            #[allow(deprecated)]
+            self.h(0)
+        }
     }
 }
 
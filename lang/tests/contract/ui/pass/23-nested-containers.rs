@@ -0,0 +1,37 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod nested {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Nested {
+        allowances: storage::Mapping<Address, storage::Mapping<Address, u128>>,
+        lists: storage::Mapping<String, storage::Vec<u32>>,
+    }
+
+    #[liquid(methods)]
+    impl Nested {
+        pub fn new(&mut self) {}
+
+        pub fn approve(&mut self, spender: Address, value: u128) {
+            let owner = self.env().get_caller();
+            self.allowances[&owner].insert(spender, value);
+        }
+
+        pub fn allowance(&self, owner: Address, spender: Address) -> u128 {
+            self.allowances[&owner].get(&spender).copied().unwrap_or(0)
+        }
+
+        pub fn push(&mut self, name: String, value: u32) {
+            self.lists[&name].push(value);
+        }
+
+        pub fn len_of(&self, name: String) -> u32 {
+            self.lists[&name].len()
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod migration {
+    use super::*;
+
+    #[liquid(storage)]
+    #[liquid(schema_version = 2)]
+    #[liquid(migration_guard = "only_owner")]
+    struct Migration {
+        #[liquid(rename_from = "count")]
+        counter: storage::Value<u64>,
+        #[liquid(migrate_from = "storage::Vec<u32>")]
+        history: storage::Vec<u64>,
+        owner: storage::Value<Address>,
+    }
+
+    #[liquid(methods)]
+    impl Migration {
+        pub fn new(&mut self) {
+            self.counter.initialize(0);
+            self.history.initialize();
+            self.owner.initialize(self.env().get_caller());
+        }
+
+        pub fn inc(&mut self) {
+            *self.counter += 1;
+            let counter = *self.counter;
+            self.history.push(counter);
+        }
+
+        pub fn get(&self) -> u64 {
+            *self.counter
+        }
+
+        fn only_owner(&self) -> bool {
+            self.env().get_caller() == *self.owner
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,51 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::interface(name = auto)]
+mod vault {
+    extern "liquid" {
+        fn new(&mut self, owner: Address, limit: u64);
+
+        fn balance(&self) -> u64;
+    }
+}
+
+#[liquid::contract]
+mod factory {
+    use super::{vault::*, *};
+
+    #[liquid(storage)]
+    struct Factory {
+        code: storage::Value<Vec<u8>>,
+        vaults: storage::Mapping<Address, Vault>,
+    }
+
+    #[liquid(methods)]
+    impl Factory {
+        pub fn new(&mut self, code: Vec<u8>) {
+            self.code.initialize(code);
+            self.vaults.initialize();
+        }
+
+        pub fn create_vault(&mut self, limit: u64) -> Address {
+            let owner = self.env().get_caller();
+            let vault = Vault::deploy(&self.code, owner.clone(), limit).unwrap();
+            self.vaults.insert(owner, vault.clone());
+            vault.into()
+        }
+
+        pub fn create_vault_with_salt(&mut self, salt: hash, limit: u64) -> Address {
+            let owner = self.env().get_caller();
+            let vault =
+                Vault::deploy_with_salt(&self.code, &salt, owner.clone(), limit).unwrap();
+            self.vaults.insert(owner, vault.clone());
+            vault.into()
+        }
+
+        pub fn balance_of(&self, owner: Address) -> u64 {
+            self.vaults[&owner].balance().unwrap_or(0)
+        }
+    }
+}
+
+fn main() {}
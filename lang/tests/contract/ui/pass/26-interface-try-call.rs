@@ -0,0 +1,48 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::interface(name = auto)]
+mod token {
+    extern "liquid" {
+        fn balance_of(&self, owner: Address) -> u64;
+
+        fn transfer(&mut self, to: Address, amount: u64) -> bool;
+    }
+}
+
+#[liquid::contract]
+mod wallet {
+    use super::{token::*, *};
+
+    #[liquid(storage)]
+    struct Wallet {
+        token: storage::Value<Token>,
+        last_error: storage::Value<String>,
+    }
+
+    #[liquid(methods)]
+    impl Wallet {
+        pub fn new(&mut self, token: Address) {
+            self.token.initialize(Token::at(token));
+            self.last_error.initialize(String::new());
+        }
+
+        pub fn balance(&self) -> u64 {
+            let owner = self.env().get_address();
+            self.token.try_balance_of(owner).unwrap_or(0)
+        }
+
+        pub fn pay(&mut self, to: Address, amount: u64) -> bool {
+            match self.token.try_transfer(to, amount) {
+                Ok(success) => success,
+                Err(liquid_lang::env::error::EnvError::CallReverted(msg)) => {
+                    self.last_error.set(msg);
+                    false
+                }
+                Err(_) => false,
+            }
+        }
+    }
+}
+
+fn main() {}
@@ -18,7 +18,9 @@ fn compile_tests() {
     t.pass("tests/derive/ui/pass/02-empty-struct.rs");
     t.pass("tests/derive/ui/pass/03-enum.rs");
     t.pass("tests/derive/ui/pass/04-nested.rs");
+    t.pass("tests/derive/ui/pass/05-storage-layout.rs");
     t.compile_fail("tests/derive/ui/fail/01-union.rs");
     t.compile_fail("tests/derive/ui/fail/02-not-public.rs");
     t.compile_fail("tests/derive/ui/fail/03-generic.rs");
+    t.compile_fail("tests/derive/ui/fail/04-storage-layout-enum.rs");
 }
@@ -0,0 +1,9 @@
+use liquid::{storage, StorageLayout};
+use liquid_lang as liquid;
+
+#[derive(StorageLayout)]
+pub enum MyEnum {
+    A(storage::Value<u8>),
+}
+
+fn main() {}
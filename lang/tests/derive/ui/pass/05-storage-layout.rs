@@ -0,0 +1,60 @@
+use liquid::{storage, StorageLayout};
+use liquid_lang as liquid;
+
+#[derive(StorageLayout)]
+pub struct Config {
+    owner: storage::Value<String>,
+    threshold: storage::Value<u32>,
+}
+
+#[derive(StorageLayout)]
+pub struct Proposal {
+    title: storage::Value<String>,
+    votes: storage::Mapping<String, bool>,
+    comments: storage::Vec<String>,
+}
+
+#[derive(StorageLayout)]
+pub struct Counter(storage::Value<u64>);
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Noop {
+        config: Config,
+        proposals: storage::Mapping<u32, Proposal>,
+        counters: storage::Mapping<String, Counter>,
+    }
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {
+            self.config.owner.initialize(String::from("Alice"));
+            self.config.threshold.initialize(2);
+        }
+
+        pub fn propose(&mut self, id: u32, title: String) {
+            self.proposals[&id].title.set(title);
+        }
+
+        pub fn vote(&mut self, id: u32, voter: String) -> u32 {
+            let proposal = &mut self.proposals[&id];
+            proposal.votes.insert(voter.clone(), true);
+            proposal.comments.push(voter);
+            self.counters[&String::from("votes")].0 += 1;
+            proposal.votes.len()
+        }
+
+        pub fn title(&self, id: u32) -> String {
+            self.proposals[&id].title.get().clone()
+        }
+
+        pub fn threshold(&self) -> u32 {
+            *self.config.threshold.get()
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,166 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod counter {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Counter {
+        value: storage::Value<u32>,
+    }
+
+    #[liquid(methods)]
+    impl Counter {
+        pub fn new(&mut self, init: u32) {
+            self.value.initialize(init);
+        }
+
+        pub fn get(&self) -> u32 {
+            *self.value
+        }
+
+        pub fn set(&mut self, value: u32) {
+            *self.value = value;
+        }
+
+        pub fn add(&self, a: u32, b: u32) -> u32 {
+            self.offset(a) + b
+        }
+
+        fn offset(&self, a: u32) -> u32 {
+            a
+        }
+    }
+}
+
+// A second contract in the same crate, whose generated items must not clash
+// with the ones of `counter`.
+#[liquid::contract]
+mod greeter {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Greeter {
+        greeting: storage::Value<String>,
+    }
+
+    #[liquid(methods)]
+    impl Greeter {
+        pub fn new(&mut self) {
+            self.greeting.initialize(String::from("hello"));
+        }
+
+        pub fn get(&self) -> String {
+            self.greeting.clone()
+        }
+    }
+}
+
+use counter::Counter;
+use greeter::Greeter;
+use liquid::env::{self, test, test::ContractEntry};
+use liquid_primitives::Selector;
+use scale::Encode;
+
+fn selector_of(name: &str) -> [u8; 4] {
+    let hash = liquid_primitives::hash::hash(name.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+fn call_data<P: Encode>(name: &str, params: P) -> Vec<u8> {
+    let mut data = selector_of(name).to_vec();
+    data.extend(params.encode());
+    data
+}
+
+#[test]
+fn dispatch_by_selector() {
+    let accounts = test::default_accounts();
+    test::deploy_contract::<Counter, _>(accounts.bob.clone(), (7u32,)).unwrap();
+    test::deploy_contract::<Greeter, _>(accounts.charlie.clone(), ()).unwrap();
+
+    assert_eq!(
+        test::call_raw(&accounts.bob, &call_data("get", ())),
+        Ok(7u32.encode())
+    );
+    assert_eq!(
+        test::call_raw(&accounts.bob, &call_data("set", (42u32,))),
+        Ok(vec![])
+    );
+    assert_eq!(
+        test::call_raw(&accounts.bob, &call_data("get", ())),
+        Ok(42u32.encode())
+    );
+    assert_eq!(
+        test::call_raw(&accounts.charlie, &call_data("get", ())),
+        Ok(String::from("hello").encode())
+    );
+
+    let _guard = test::as_caller(accounts.alice);
+    assert_eq!(
+        env::call::<u32>(&accounts.bob, &call_data("add", (40u32, 2u32))).unwrap(),
+        42
+    );
+}
+
+#[test]
+fn dispatch_errors() {
+    let accounts = test::default_accounts();
+    test::deploy_contract::<Counter, _>(accounts.bob.clone(), (7u32,)).unwrap();
+
+    let mut data = selector_of("add").to_vec();
+    data.extend_from_slice(&[1, 0]);
+    assert_eq!(
+        test::call_raw(&accounts.bob, &data),
+        Err(String::from("invalid params for `a`: 0100").encode())
+    );
+
+    let mut data = call_data("add", (1u32,));
+    data.push(2);
+    assert_eq!(
+        test::call_raw(&accounts.bob, &data),
+        Err(String::from("invalid params for `b`: 02").encode())
+    );
+
+    assert_eq!(
+        test::call_raw(&accounts.bob, &[0xde, 0xad, 0xbe, 0xef]),
+        Err(String::from("unknown selector: deadbeef").encode())
+    );
+
+    // Private methods are not dispatched.
+    let offset = selector_of("offset");
+    assert_eq!(
+        test::call_raw(&accounts.bob, &call_data("offset", (1u32,))),
+        Err(format!("unknown selector: {}", liquid::bytes_to_hex(&offset)).encode())
+    );
+}
+
+#[test]
+fn method_names() {
+    let name_of = |name| Counter::method_name(Selector::from_le_bytes(selector_of(name)));
+    assert_eq!(name_of("get"), Some("get"));
+    assert_eq!(name_of("set"), Some("set"));
+    assert_eq!(name_of("add"), Some("add"));
+    assert_eq!(name_of("offset"), None);
+    assert_eq!(name_of("new"), None);
+
+    let accounts = test::default_accounts();
+    test::deploy_contract::<Counter, _>(accounts.bob.clone(), (7u32,)).unwrap();
+    test::call_raw(&accounts.bob, &call_data("get", ())).unwrap();
+    test::call_raw(&accounts.bob, &call_data("get", ())).unwrap();
+    let report = test::gas_report();
+    assert_eq!(report[&(accounts.bob, String::from("get"))].invocations, 2);
+}
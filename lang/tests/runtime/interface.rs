@@ -0,0 +1,141 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use liquid::storage::{self, Bind, Flush};
+use liquid_lang as liquid;
+
+#[liquid::interface(name = auto)]
+mod vault {
+    extern "liquid" {
+        fn withdraw(&mut self, amount: u64) -> bool;
+        fn balance(&self) -> u64;
+    }
+}
+
+#[liquid::contract]
+mod client {
+    use super::{vault::*, *};
+    use liquid::env::error::EnvError;
+
+    #[liquid(storage)]
+    struct Client {
+        vault: storage::Value<Vault>,
+    }
+
+    #[liquid(methods)]
+    impl Client {
+        pub fn new(&mut self, vault: Address) {
+            self.vault.initialize(Vault::at(vault));
+        }
+
+        pub fn withdraw(&mut self, amount: u64) -> String {
+            match self.vault.try_withdraw(amount) {
+                Ok(true) => String::from("ok"),
+                Ok(false) => String::from("rejected"),
+                Err(EnvError::CallReverted(msg)) => msg,
+                Err(_) => String::from("failed"),
+            }
+        }
+
+        pub fn balance(&self) -> String {
+            match self.vault.try_balance() {
+                Ok(balance) => balance.to_string(),
+                Err(EnvError::CallReverted(msg)) => msg,
+                Err(_) => String::from("failed"),
+            }
+        }
+    }
+}
+
+use client::Client;
+use liquid::env::{error::EnvError, test};
+use vault::Vault;
+
+#[test]
+fn try_variant_returns_revert_message() {
+    let accounts = test::default_accounts();
+    let _guard = test::as_caller(accounts.alice);
+    let mut client = Client::new(accounts.bob.clone());
+
+    let ctx = Vault::withdraw_context();
+    ctx.expect().when_fn(|amount| *amount <= 100).returns(true);
+    ctx.expect().reverts("insufficient balance");
+
+    assert_eq!(client.withdraw(100), "ok");
+    assert_eq!(client.withdraw(101), "insufficient balance");
+
+    let mut vault = Vault::at(accounts.bob);
+    assert!(matches!(
+        vault.try_withdraw(101),
+        Err(EnvError::CallReverted(msg)) if msg == "insufficient balance"
+    ));
+    assert_eq!(vault.withdraw(101), None);
+    assert_eq!(vault.withdraw(1), Some(true));
+}
+
+#[test]
+fn try_variant_returns_call_failure() {
+    let accounts = test::default_accounts();
+    let _guard = test::as_caller(accounts.alice);
+    let mut client = Client::new(accounts.bob.clone());
+
+    let ctx = Vault::withdraw_context();
+    ctx.expect().throws();
+
+    assert_eq!(client.withdraw(1), "failed");
+    assert!(matches!(
+        Vault::at(accounts.bob).try_withdraw(1),
+        Err(EnvError::FailToCallForeignContract)
+    ));
+}
+
+/// Writes `value` to the storage of current contract.
+fn write_cache(value: u64) {
+    let mut cache = storage::Value::<u64>::bind_with(b"cache");
+    cache.initialize(value);
+    cache.flush();
+}
+
+#[test]
+fn static_call_rejects_writes() {
+    let accounts = test::default_accounts();
+    let _guard = test::as_caller(accounts.alice);
+    let mut client = Client::new(accounts.bob.clone());
+
+    // `balance` takes `&self`, hence it's called via a static call in which
+    // the state can't be modified.
+    let balance_ctx = Vault::balance_context();
+    balance_ctx.expect().returns_fn(|| {
+        write_cache(100);
+        100
+    });
+    assert_eq!(client.balance(), "cannot modify the state in a static call");
+    assert!(matches!(
+        Vault::at(accounts.bob.clone()).try_balance(),
+        Err(EnvError::CallReverted(msg)) if msg == "cannot modify the state in a static call"
+    ));
+
+    let withdraw_ctx = Vault::withdraw_context();
+    withdraw_ctx.expect().returns_fn(|amount| {
+        write_cache(amount);
+        true
+    });
+    assert_eq!(client.withdraw(1), "ok");
+
+    // Reading the state is still allowed in static calls.
+    drop(balance_ctx);
+    let balance_ctx = Vault::balance_context();
+    balance_ctx
+        .expect()
+        .returns_fn(|| *storage::Value::<u64>::bind_with(b"cache"));
+    assert_eq!(client.balance(), "1");
+}
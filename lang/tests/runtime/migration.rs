@@ -0,0 +1,131 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use liquid::storage::{self, Bind, Flush};
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod migration {
+    use super::*;
+
+    #[liquid(storage)]
+    #[liquid(schema_version = 2)]
+    #[liquid(migration_guard = "only_owner")]
+    struct Migration {
+        #[liquid(rename_from = "count")]
+        counter: storage::Value<u64>,
+        #[liquid(migrate_from = "storage::Vec<u32>")]
+        history: storage::Vec<u64>,
+        owner: storage::Value<Address>,
+    }
+
+    #[liquid(methods)]
+    impl Migration {
+        pub fn new(&mut self) {
+            self.counter.initialize(0);
+            self.history.initialize();
+            self.owner.initialize(self.env().get_caller());
+        }
+
+        pub fn inc(&mut self) {
+            *self.counter += 1;
+            let counter = *self.counter;
+            self.history.push(counter);
+        }
+
+        pub fn get(&self) -> u64 {
+            *self.counter
+        }
+
+        pub fn history(&self) -> Vec<u64> {
+            self.history.iter().cloned().collect()
+        }
+
+        fn only_owner(&self) -> bool {
+            self.env().get_caller() == *self.owner
+        }
+    }
+}
+
+use liquid::env::test;
+use liquid_primitives::types::Address;
+use migration::Migration;
+
+/// Writes the storage in the layout of the first version of the contract,
+/// which names the counter `count` and records the history as `u32`.
+fn write_v1_storage(owner: Address) {
+    let mut count = storage::Value::<u64>::bind_with(b"count");
+    count.initialize(3);
+    count.flush();
+
+    let mut history = storage::Vec::<u32>::bind_with(b"history");
+    history.initialize();
+    for i in 1..=3 {
+        history.push(i);
+    }
+    history.flush();
+
+    let mut owner_value = storage::Value::<Address>::bind_with(b"owner");
+    owner_value.initialize(owner);
+    owner_value.flush();
+}
+
+#[test]
+fn migrate_v1_storage() {
+    let accounts = test::default_accounts();
+    let _guard = test::as_caller(accounts.alice.clone());
+    write_v1_storage(accounts.alice);
+    assert_eq!(storage::schema_version(), 0);
+
+    let mut contract = Migration::load();
+    // The renamed field is bound with its old key, so it's readable without
+    // any migration.
+    assert_eq!(contract.get(), 3);
+
+    assert!(contract.migrate_schema());
+    assert_eq!(storage::schema_version(), 2);
+    assert_eq!(contract.history(), vec![1, 2, 3]);
+
+    contract.inc();
+    assert_eq!(contract.get(), 4);
+    assert_eq!(contract.history(), vec![1, 2, 3, 4]);
+
+    // The migration only runs once.
+    assert!(!contract.migrate_schema());
+    assert_eq!(contract.history(), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn migrate_by_others() {
+    let accounts = test::default_accounts();
+    write_v1_storage(accounts.alice);
+
+    let _guard = test::as_caller(accounts.bob);
+    let mut contract = Migration::load();
+    let result = test::catch_revert(|| contract.migrate_schema());
+    assert_eq!(
+        result,
+        Err(test::Reverted(
+            "the caller is not allowed to migrate the storage".into()
+        ))
+    );
+    assert_eq!(storage::schema_version(), 0);
+}
+
+#[test]
+fn deploy_records_schema_version() {
+    let accounts = test::default_accounts();
+    let _guard = test::as_caller(accounts.alice);
+    let contract = Migration::new();
+    assert_eq!(storage::schema_version(), 2);
+    assert_eq!(contract.get(), 0);
+}
@@ -0,0 +1,98 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use liquid::{storage, StorageLayout};
+use liquid_lang as liquid;
+
+#[derive(StorageLayout)]
+pub struct Profile {
+    name: storage::Value<String>,
+    age: storage::Value<u32>,
+}
+
+#[liquid::contract]
+mod registry {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Registry {
+        profiles: storage::Mapping<u32, Profile>,
+    }
+
+    #[liquid(methods)]
+    impl Registry {
+        pub fn new(&mut self) {
+            self.profiles.initialize();
+        }
+
+        pub fn register(&mut self, id: u32, name: String, age: u32) {
+            let profile = &mut self.profiles[&id];
+            profile.name.set(name);
+            profile.age.set(age);
+        }
+
+        pub fn set_age(&mut self, id: u32, age: u32) {
+            self.profiles[&id].age.set(age);
+        }
+
+        pub fn profile(&self, id: u32) -> (String, u32) {
+            let profile = &self.profiles[&id];
+            (profile.name.get().clone(), *profile.age.get())
+        }
+    }
+}
+
+use liquid::env::test::{self, StorageChange};
+use registry::Registry;
+use scale::Encode;
+
+fn call_data<P: Encode>(name: &str, params: P) -> Vec<u8> {
+    let hash = liquid_primitives::hash::hash(name.as_bytes());
+    let mut data = hash[..4].to_vec();
+    data.extend(params.encode());
+    data
+}
+
+#[test]
+fn update_single_field() {
+    let accounts = test::default_accounts();
+    let registry = accounts.bob;
+    test::deploy_contract::<Registry, _>(registry.clone(), ()).unwrap();
+    test::call_raw(
+        &registry,
+        &call_data("register", (1u32, String::from("Alice"), 20u32)),
+    )
+    .unwrap();
+
+    let before = test::storage_snapshot_of(&registry);
+    let (ret, usage) =
+        test::metered(|| test::call_raw(&registry, &call_data("set_age", (1u32, 21u32))));
+    assert_eq!(ret, Ok(vec![]));
+    assert_eq!(usage.storage_writes, 1);
+    assert_eq!(usage.storage_removes, 0);
+
+    // Only the updated field is written, while the other field of the struct
+    // and the length of the mapping are left untouched.
+    let after = test::storage_snapshot_of(&registry);
+    let changes = test::storage_diff(&before, &after);
+    assert_eq!(changes.len(), 1);
+    assert!(matches!(
+        &changes[0],
+        StorageChange::Updated { old, new, .. }
+            if *old == 20u32.encode() && *new == 21u32.encode()
+    ));
+
+    assert_eq!(
+        test::call_raw(&registry, &call_data("profile", (1u32,))),
+        Ok((String::from("Alice"), 21u32).encode())
+    );
+}
@@ -12,12 +12,16 @@
 
 use cfg_if::cfg_if;
 
+mod keccak;
+mod sm3;
+
+pub use keccak::keccak256;
+pub use sm3::sm3;
+
 cfg_if! {
     if #[cfg(not(feature = "gm"))] {
-        mod keccak;
         pub use keccak::keccak256 as hash;
     } else {
-        mod sm3;
         pub use sm3::sm3 as hash;
     }
 }
@@ -57,6 +57,17 @@ impl std::fmt::Display for Error {
 
 pub trait Topics {
     fn topics(&self) -> Vec<types::Hash>;
+
+    /// Returns the encoded values of all fields in declaration order, including
+    /// the indexed ones which are recorded as topics only.
+    ///
+    /// It's used by the test environment to decode emitted events, hence it's
+    /// not available on chain.
+    #[cfg(feature = "std")]
+    #[doc(hidden)]
+    fn encode_fields(&self) -> Vec<u8> {
+        Vec::new()
+    }
 }
 
 cfg_if! {
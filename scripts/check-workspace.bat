@@ -90,6 +90,11 @@ for %%f in %features% do (
     )
 )
 
+cargo test --verbose --features "contract off-chain-crypto" --release --manifest-path lang/Cargo.toml
+if !errorlevel! neq 0 (
+    set results[4].result=1
+)
+
 cargo test --verbose --features "collaboration" --release --manifest-path lang/macro/Cargo.toml
 if !errorlevel! neq 0 (
     set results[4].result=1
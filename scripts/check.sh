@@ -79,7 +79,7 @@ check_workspace() {
     LOG_INFO "checking workspace build fmt ..."
     cargo fmt --verbose --all -- --check
     LOG_INFO "checking workspace build unit test ..."
-    cargo test --verbose --features "contract" --release --manifest-path lang/Cargo.toml
+    cargo test --verbose --features "contract off-chain-crypto" --release --manifest-path lang/Cargo.toml
     cargo test --verbose --features "collaboration" --release --manifest-path lang/Cargo.toml
     cargo test --verbose --release --manifest-path primitives/Cargo.toml
     cargo test --verbose --release --features "collaboration" --manifest-path lang/macro/Cargo.toml